@@ -0,0 +1,194 @@
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::ptr::null_mut;
+
+use crate::spinlock::Spinlock;
+
+// Allocateur par blocs compagnons (« buddy allocator »).
+
+// Chaque bloc a une taille puissance de deux et une position multiple de sa taille
+// par rapport au début du tas. Un bloc trop grand est coupé en deux compagnons ;
+// à la libération, un bloc dont le compagnon est libre fusionne avec lui.
+
+/// Ordre du plus petit bloc : 16 octets, de quoi ranger le chaînage d'un bloc libre.
+pub const MIN_ORDER: usize = 4;
+
+// Nombre d'ordres possibles, du plus petit bloc à la taille de l'espace d'adressage
+const ORDERS: usize = usize::BITS as usize - MIN_ORDER;
+
+// En-tête d'un bloc libre, rangé au début du bloc.
+struct FreeNode {
+    next: *mut FreeNode, // Bloc libre suivant du même ordre
+}
+
+pub struct BuddyAllocator {
+    free_lists: Spinlock<[*mut FreeNode; ORDERS]>, // Une liste par ordre ; le verrou protège tout l'état
+    base: Cell<usize>,                             // Début du tas, origine du calcul des compagnons
+    base_align: Cell<usize>,                       // Plus grand alignement garanti par `base`
+}
+
+/// # Safety
+/// Toutes les modifications des listes et des champs se font sous le verrou de `free_lists`.
+unsafe impl Sync for BuddyAllocator {}
+
+impl Default for BuddyAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for BuddyAllocator {
+    /// # Safety
+    /// Mêmes exigences que `GlobalAlloc::alloc`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let Some(order) = Self::order_of(layout) else {
+            return null_mut();
+        };
+
+        let _guard = self.free_lists.lock();
+        let lists = &mut *self.free_lists.get();
+
+        // Les blocs ne sont alignés que relativement au début du tas
+        if layout.align() > self.base_align.get() {
+            return null_mut();
+        }
+
+        // Plus petit ordre disponible au moins égal à celui demandé
+        let Some(mut current) = (order..ORDERS).find(|&i| !lists[i].is_null()) else {
+            return null_mut();
+        };
+        let block = Self::pop(&mut lists[current]);
+
+        // Découpage : la moitié haute devient un bloc libre de l'ordre inférieur
+        while current > order {
+            current -= 1;
+            Self::push(&mut lists[current], block as usize + Self::block_size(current));
+        }
+
+        block as *mut u8
+    }
+
+    /// # Safety
+    /// `ptr` doit provenir de cet allocateur avec le même `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let Some(mut order) = Self::order_of(layout) else {
+            return;
+        };
+
+        let _guard = self.free_lists.lock();
+        let lists = &mut *self.free_lists.get();
+        let base = self.base.get();
+        let mut addr = ptr as usize;
+
+        // Fusion tant que le compagnon du bloc est libre
+        while order + 1 < ORDERS {
+            let buddy = base + ((addr - base) ^ Self::block_size(order));
+            if !Self::remove(&mut lists[order], buddy) {
+                break;
+            }
+
+            addr = addr.min(buddy);
+            order += 1;
+        }
+
+        Self::push(&mut lists[order], addr);
+    }
+}
+
+impl BuddyAllocator {
+    /// Crée un allocateur vide ; `init` lui confie son tas.
+    pub const fn new() -> Self {
+        BuddyAllocator {
+            free_lists: Spinlock::new([null_mut(); ORDERS]),
+            base: Cell::new(0),
+            base_align: Cell::new(0),
+        }
+    }
+
+    /// # Safety
+    /// Confie la région `heap_start..heap_start + heap_size` à l'allocateur, découpée en
+    /// blocs puissances de deux aussi grands que possible. La région doit être valide,
+    /// inutilisée et alignée sur au moins 16 octets ; un seul appel est permis.
+    pub unsafe fn init(&self, heap_start: usize, heap_size: usize) {
+        let _guard = self.free_lists.lock();
+        let lists = &mut *self.free_lists.get();
+
+        self.base.set(heap_start);
+        self.base_align.set(1 << heap_start.trailing_zeros().min(usize::BITS - 1));
+
+        let mut offset = 0;
+        while heap_size - offset >= Self::block_size(0) {
+            // Plus grand bloc qui tient dans le reste et dont la position est multiple de sa taille
+            let order = (0..ORDERS)
+                .rev()
+                .find(|&i| Self::block_size(i) <= heap_size - offset && offset % Self::block_size(i) == 0)
+                .unwrap_or(0);
+
+            Self::push(&mut lists[order], heap_start + offset);
+            offset += Self::block_size(order);
+        }
+    }
+
+    /// Retourne le nombre de blocs libres de `size` octets, ou 0 si `size` n'est pas
+    /// la taille d'un ordre.
+    pub fn free_blocks_of_size(&self, size: usize) -> usize {
+        if !size.is_power_of_two() || size < Self::block_size(0) {
+            return 0;
+        }
+
+        let _guard = self.free_lists.lock();
+        let mut current = unsafe { (*self.free_lists.get())[size.trailing_zeros() as usize - MIN_ORDER] };
+        let mut count = 0;
+
+        while !current.is_null() {
+            count += 1;
+            current = unsafe { (*current).next };
+        }
+
+        count
+    }
+
+    /// Retourne l'ordre du plus petit bloc pouvant contenir `layout`, ou `None` s'il dépasse
+    /// l'espace d'adressage.
+    fn order_of(layout: Layout) -> Option<usize> {
+        let size = layout.size().max(layout.align()).max(Self::block_size(0));
+        Some(size.checked_next_power_of_two()?.trailing_zeros() as usize - MIN_ORDER)
+    }
+
+    /// Retourne la taille d'un bloc d'ordre `order`.
+    const fn block_size(order: usize) -> usize {
+        1 << (order + MIN_ORDER)
+    }
+
+    /// # Safety
+    /// Ajoute en tête de `list` le bloc libre commençant à `addr`.
+    unsafe fn push(list: &mut *mut FreeNode, addr: usize) {
+        let node = addr as *mut FreeNode;
+        (*node).next = *list;
+        *list = node;
+    }
+
+    /// # Safety
+    /// Retire et retourne le bloc de tête de `list`, qui ne doit pas être vide.
+    unsafe fn pop(list: &mut *mut FreeNode) -> *mut FreeNode {
+        let node = *list;
+        *list = (*node).next;
+        node
+    }
+
+    /// # Safety
+    /// Retire de `list` le bloc commençant à `addr` et indique s'il y était.
+    unsafe fn remove(list: &mut *mut FreeNode, addr: usize) -> bool {
+        let mut link: *mut *mut FreeNode = list;
+
+        while !(*link).is_null() {
+            if *link as usize == addr {
+                *link = (**link).next;
+                return true;
+            }
+            link = &mut (**link).next;
+        }
+
+        false
+    }
+}
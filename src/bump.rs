@@ -0,0 +1,86 @@
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::ptr::null_mut;
+
+use crate::spinlock::Spinlock;
+
+// Allocateur linéaire (« bump allocator »).
+
+// Les allocations sont prises à la suite dans la région confiée à `init` : un seul
+// pointeur avance à chaque requête. Les libérations individuelles sont ignorées ;
+// `reset` rend toute la région d'un coup, pour des allocations libérées ensemble.
+
+pub struct BumpAllocator {
+    next: Spinlock<usize>, // Adresse de la prochaine allocation ; le verrou protège tout l'état
+    start: Cell<usize>,    // Début de la région
+    end: Cell<usize>,      // Fin de la région
+}
+
+/// # Safety
+/// Toutes les modifications du pointeur et des champs se font sous le verrou de `next`.
+unsafe impl Sync for BumpAllocator {}
+
+impl Default for BumpAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    /// # Safety
+    /// Mêmes exigences que `GlobalAlloc::alloc`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _guard = self.next.lock();
+        let next = &mut *self.next.get();
+
+        let Some(address) = next.checked_next_multiple_of(layout.align()) else {
+            return null_mut();
+        };
+        match address.checked_add(layout.size()) {
+            Some(allocation_end) if allocation_end <= self.end.get() => {
+                *next = allocation_end;
+                address as *mut u8
+            }
+            _ => null_mut(),
+        }
+    }
+
+    /// # Safety
+    /// Sans effet : la mémoire n'est rendue que par `reset`.
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+impl BumpAllocator {
+    /// Crée un allocateur vide ; `init` lui confie sa région.
+    pub const fn new() -> Self {
+        BumpAllocator {
+            next: Spinlock::new(0),
+            start: Cell::new(0),
+            end: Cell::new(0),
+        }
+    }
+
+    /// # Safety
+    /// Confie la région `heap_start..heap_start + heap_size` à l'allocateur. La région doit
+    /// être valide et inutilisée ; un nouvel appel abandonne toutes les allocations en cours.
+    pub unsafe fn init(&self, heap_start: usize, heap_size: usize) {
+        let _guard = self.next.lock();
+        *self.next.get() = heap_start;
+        self.start.set(heap_start);
+        self.end.set(heap_start + heap_size);
+    }
+
+    /// # Safety
+    /// Rend toute la région : les allocations suivantes repartent de son début. L'appelant
+    /// garantit qu'aucune allocation antérieure ne sera plus utilisée.
+    pub unsafe fn reset(&self) {
+        let _guard = self.next.lock();
+        *self.next.get() = self.start.get();
+    }
+
+    /// Retourne le nombre d'octets encore disponibles après la dernière allocation.
+    pub fn remaining(&self) -> usize {
+        let _guard = self.next.lock();
+        self.end.get() - unsafe { *self.next.get() }
+    }
+}
@@ -0,0 +1,60 @@
+use core::alloc::{GlobalAlloc, Layout};
+
+// Chaîne de deux allocateurs.
+
+// Les allocations sont d'abord demandées au premier ; quand il échoue, le second
+// prend le relais. Chaque libération retourne à l'allocateur dont la mémoire
+// contient le pointeur, d'où le trait `Owns`.
+
+/// Allocateur capable de dire si un pointeur appartient à la mémoire qu'il gère.
+pub trait Owns {
+    /// Indique si `ptr` se trouve dans l'une des régions confiées à l'allocateur.
+    fn owns(&self, ptr: *const u8) -> bool;
+}
+
+pub struct Fallback<A, B> {
+    primary: A,   // Allocateur essayé en premier
+    secondary: B, // Allocateur de secours
+}
+
+impl<A, B> Fallback<A, B> {
+    /// Chaîne `primary` puis `secondary`.
+    pub const fn new(primary: A, secondary: B) -> Self {
+        Fallback { primary, secondary }
+    }
+
+    /// Retourne l'allocateur essayé en premier, par exemple pour l'initialiser.
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    /// Retourne l'allocateur de secours.
+    pub fn secondary(&self) -> &B {
+        &self.secondary
+    }
+}
+
+unsafe impl<A: GlobalAlloc + Owns, B: GlobalAlloc + Owns> GlobalAlloc for Fallback<A, B> {
+    /// # Safety
+    /// Mêmes exigences que `GlobalAlloc::alloc`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.primary.alloc(layout);
+        if ptr.is_null() {
+            self.secondary.alloc(layout)
+        } else {
+            ptr
+        }
+    }
+
+    /// # Safety
+    /// `ptr` doit provenir de cette chaîne avec le même `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if self.primary.owns(ptr) {
+            self.primary.dealloc(ptr, layout);
+        } else {
+            // Les allocations de taille nulle n'appartiennent à aucune région
+            debug_assert!(layout.size() == 0 || self.secondary.owns(ptr), "pointeur étranger à la chaîne : {:p}", ptr);
+            self.secondary.dealloc(ptr, layout);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use core::cell::UnsafeCell;
+use core::mem::{self, MaybeUninit};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{FreeListAllocator, HEAP_ALIGN};
+
+// Zone mémoire statique destinée à servir de tas.
+
+// Contrairement à un `static mut HEAP: [u8; N]`, l'adresse de la zone s'obtient
+// sans jamais créer de référence vers un `static mut`. L'alignement garantit que
+// `insert_free_region` accepte la région entière.
+
+#[repr(C, align(16))]
+pub struct StaticHeap<const N: usize> {
+    storage: UnsafeCell<[u8; N]>, // Octets du tas, initialement à zéro
+}
+
+/// # Safety
+/// Le contenu n'est jamais accédé à travers `StaticHeap` : seule son adresse est
+/// transmise à l'allocateur, qui en devient responsable.
+unsafe impl<const N: usize> Sync for StaticHeap<N> {}
+
+impl<const N: usize> StaticHeap<N> {
+    /// Crée une zone de `N` octets remplie de zéros.
+    pub const fn new() -> Self {
+        StaticHeap {
+            storage: UnsafeCell::new([0; N]),
+        }
+    }
+
+    /// Retourne l'adresse de début et la taille de la zone, à passer à `init`.
+    pub fn as_region(&self) -> (usize, usize) {
+        (self.storage.get() as usize, N)
+    }
+}
+
+impl<const N: usize> Default for StaticHeap<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Tableau d'octets aligné sur `HEAP_ALIGN`.
+
+// À déclarer en `static mut` ou sur la pile à la place d'un `[u8; N]`, dont l'alignement
+// de 1 ferait perdre à `init` jusqu'à `HEAP_ALIGN - 1` octets en tête de région.
+
+#[repr(C, align(16))]
+pub struct AlignedHeap<const N: usize>(pub [u8; N]);
+
+// L'attribut `align` n'accepte qu'un littéral : on vérifie qu'il couvre `HEAP_ALIGN`
+const _: () = assert!(mem::align_of::<AlignedHeap<0>>() >= HEAP_ALIGN);
+
+impl<const N: usize> AlignedHeap<N> {
+    /// Crée un tableau de `N` octets à zéro.
+    pub const fn new() -> Self {
+        AlignedHeap([0; N])
+    }
+
+    /// Retourne l'adresse de début et la taille du tableau, à passer à `init`.
+    pub fn as_region(&mut self) -> (usize, usize) {
+        (self.0.as_mut_ptr() as usize, N)
+    }
+}
+
+impl<const N: usize> Default for AlignedHeap<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Tas statique qui se branche lui-même sur un allocateur.
+
+// Le contenu n'a pas besoin d'être initialisé : `init_allocator` aligne le début de
+// la zone puis la confie à l'allocateur, une seule fois, ce qui remplace la
+// séquence manuelle `static mut HEAP` + `init`.
+
+pub struct Heap<const N: usize> {
+    storage: UnsafeCell<MaybeUninit<[u8; N]>>, // Octets du tas, non initialisés
+    taken: AtomicBool,                         // Vrai une fois la zone confiée à un allocateur
+}
+
+/// # Safety
+/// Le contenu n'est accédé que par l'allocateur auquel `init_allocator` l'a confié,
+/// et `taken` empêche de le confier deux fois.
+unsafe impl<const N: usize> Sync for Heap<N> {}
+
+impl<const N: usize> Heap<N> {
+    /// Crée une zone de `N` octets non initialisés.
+    pub const fn new() -> Self {
+        Heap {
+            storage: UnsafeCell::new(MaybeUninit::uninit()),
+            taken: AtomicBool::new(false),
+        }
+    }
+
+    /// Confie la zone, alignée pour accueillir des blocs, à `alloc` par `init`.
+    /// Panique si la zone a déjà été confiée à un allocateur.
+    pub fn init_allocator<const MIN: usize>(&'static self, alloc: &FreeListAllocator<MIN>) {
+        let already_taken = self.taken.swap(true, Ordering::AcqRel);
+        assert!(!already_taken, "tas déjà confié à un allocateur");
+
+        let base = self.storage.get() as usize;
+        let start = base.next_multiple_of(HEAP_ALIGN).min(base + N);
+
+        // La zone est statique, inutilisée et n'appartient qu'à cet allocateur
+        unsafe { alloc.init(start, base + N - start) };
+    }
+}
+
+impl<const N: usize> Default for Heap<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
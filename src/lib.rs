@@ -0,0 +1,3326 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::ptr::{self, null_mut, NonNull};
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem;
+
+mod buddy;
+mod bump;
+mod fallback;
+mod heap;
+mod logging;
+#[cfg(feature = "std")]
+mod magazine;
+mod out_of_line;
+mod slab;
+mod spinlock;
+mod workload;
+
+pub use buddy::BuddyAllocator;
+pub use bump::BumpAllocator;
+pub use fallback::{Fallback, Owns};
+pub use heap::{AlignedHeap, Heap, StaticHeap};
+pub use logging::{Event, Logging};
+#[cfg(feature = "std")]
+pub use magazine::{Magazine, MAGAZINE_SIZE};
+pub use out_of_line::{FreeRange, OutOfLineAllocator};
+pub use slab::Slab;
+pub use workload::{Op, WorkloadStats, MAX_WORKLOAD_ALLOCS};
+use spinlock::Spinlock;
+
+
+// Représente un bloc de mémoire libre dans la liste chaînée.
+
+// Ce bloc est utilisé par l'allocateur pour suivre les régions
+// de mémoire non utilisées.
+
+
+#[cfg(not(feature = "small_header"))]
+#[repr(C)]
+pub struct Block {
+    size: usize,              // Taille du bloc
+    next: *mut Block,         // Pointeur vers le prochain bloc
+    dirty: bool,              // Le contenu a pu être écrit depuis l'initialisation
+    region: u8,               // Indice de la région d'origine, `NO_REGION` hors des régions
+}
+
+// En-tête réduit : taille sur 32 bits et bloc suivant repéré par son écart au bloc courant.
+// Les blocs ne dépassent pas `HEAP_MAX` octets et toutes les régions doivent tenir dans
+// une plage de `HEAP_MAX` octets, pour que l'écart entre deux blocs tienne sur 32 bits.
+#[cfg(feature = "small_header")]
+#[repr(C)]
+pub struct Block {
+    size: u32,                // Taille du bloc
+    next: i32,                // Écart en octets jusqu'au prochain bloc, 0 en fin de liste
+    dirty: bool,              // Le contenu a pu être écrit depuis l'initialisation
+    region: u8,               // Indice de la région d'origine, `NO_REGION` hors des régions
+}
+
+/// Plus grande taille de région avec `small_header` (1 Gio) : le tas entier, toutes régions
+/// comprises, doit aussi tenir dans une plage de cette taille. Une région plus grande est
+/// réduite à ses `HEAP_MAX` premiers octets.
+#[cfg(feature = "small_header")]
+pub const HEAP_MAX: usize = 1 << 30;
+
+/// Alignement à donner au début d'un tas pour que `init` en enregistre chaque octet ;
+/// `AlignedHeap` le garantit pour un tableau statique.
+pub const HEAP_ALIGN: usize = mem::align_of::<Block>();
+
+// Indice de région des blocs confiés par `insert_free_region`, hors de toute région enregistrée.
+const NO_REGION: u8 = u8::MAX;
+
+impl Block {
+    /// Retourne l'adresse de début de ce bloc.
+    fn starting_addr(&self) -> usize {
+        self as *const Block as usize
+    }
+
+    /// Retourne l'adresse de fin de ce bloc, bornée à `usize::MAX` si une taille corrompue
+    /// l'emmène au-delà de l'espace d'adressage.
+    fn finishing_addr(&self) -> usize {
+        self.starting_addr().saturating_add(self.size())
+    }
+
+    /// Retourne l'adresse de fin de ce bloc, ou `None` si elle dépasse l'espace d'adressage.
+    fn checked_finishing_addr(&self) -> Option<usize> {
+        self.starting_addr().checked_add(self.size())
+    }
+
+    /// Retourne la taille du bloc, en-tête compris.
+    #[cfg(not(feature = "small_header"))]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Fixe la taille du bloc, en-tête compris.
+    #[cfg(not(feature = "small_header"))]
+    fn set_size(&mut self, size: usize) {
+        self.size = size;
+    }
+
+    /// Retourne le bloc suivant de la liste, ou un pointeur nul en fin de liste.
+    #[cfg(not(feature = "small_header"))]
+    pub fn next(&self) -> *mut Block {
+        self.next
+    }
+
+    /// Fixe le bloc suivant de la liste.
+    #[cfg(not(feature = "small_header"))]
+    fn set_next(&mut self, next: *mut Block) {
+        self.next = next;
+    }
+
+    /// Retourne la taille du bloc, en-tête compris.
+    #[cfg(feature = "small_header")]
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Fixe la taille du bloc, en-tête compris, qui ne doit pas dépasser `HEAP_MAX`.
+    #[cfg(feature = "small_header")]
+    fn set_size(&mut self, size: usize) {
+        debug_assert!(size <= HEAP_MAX, "bloc plus grand que HEAP_MAX : {} octets", size);
+        self.size = size as u32;
+    }
+
+    /// Retourne le bloc suivant de la liste, ou un pointeur nul en fin de liste.
+    #[cfg(feature = "small_header")]
+    pub fn next(&self) -> *mut Block {
+        match self.next {
+            0 => null_mut(),
+            offset => self.starting_addr().wrapping_add_signed(offset as isize) as *mut Block,
+        }
+    }
+
+    /// Fixe le bloc suivant de la liste, à moins de `HEAP_MAX` octets de ce bloc.
+    #[cfg(feature = "small_header")]
+    fn set_next(&mut self, next: *mut Block) {
+        let offset = (next as isize).wrapping_sub(self.starting_addr() as isize);
+        debug_assert!(next.is_null() || i32::try_from(offset).is_ok(), "bloc suivant à plus de HEAP_MAX octets : {:p}", next);
+        self.next = if next.is_null() { 0 } else { offset as i32 };
+    }
+
+    /// # Safety
+    /// Absorbe `other`, qui doit être un bloc valide commençant à `finishing_addr()`
+    /// et déjà retiré de sa liste.
+    unsafe fn absorb(&mut self, other: *mut Block) {
+        let (size, dirty) = ((*other).size(), (*other).dirty);
+
+        // L'en-tête absorbé se retrouve dans les données : on l'efface pour garder un bloc propre
+        if !self.dirty && !dirty {
+            ptr::write_bytes(other as *mut u8, 0, mem::size_of::<Block>());
+        } else {
+            #[cfg(feature = "poison")]
+            ptr::write_bytes(other as *mut u8, POISON, mem::size_of::<Block>());
+        }
+
+        self.set_size(self.size() + size);
+        self.dirty |= dirty;
+    }
+}
+
+
+// Motif écrit juste après chaque allocation pour détecter les débordements.
+#[cfg(feature = "canaries")]
+const CANARY: [u8; 8] = [0xDE, 0xAD, 0xBE, 0xEF, 0xDE, 0xAD, 0xBE, 0xEF];
+
+/// # Safety
+/// Écrit le canari après les `size` octets de données de `ptr`.
+#[cfg(feature = "canaries")]
+unsafe fn write_canary(ptr: *mut u8, size: usize) {
+    ptr::copy_nonoverlapping(CANARY.as_ptr(), ptr.add(size), CANARY.len());
+}
+
+/// # Safety
+/// Vérifie le canari écrit par `write_canary` et panique s'il a été écrasé.
+#[cfg(feature = "canaries")]
+unsafe fn check_canary(ptr: *mut u8, size: usize) {
+    if core::slice::from_raw_parts(ptr.add(size), CANARY.len()) != CANARY {
+        panic!("canari corrompu après le bloc {:#x} : débordement détecté", ptr as usize);
+    }
+}
+
+// Mode `header` : la taille ajustée de chaque allocation est rangée juste avant le pointeur
+// retourné, qui suit le début du bloc de `header_offset` octets. En mode `debug_tags`,
+// l'étiquette de l'allocation occupe le mot précédent.
+#[cfg(any(feature = "header", feature = "debug_tags"))]
+const HEADER_WORDS: usize = cfg!(feature = "header") as usize + cfg!(feature = "debug_tags") as usize;
+
+#[cfg(any(feature = "header", feature = "debug_tags"))]
+const fn header_offset(align: usize) -> usize {
+    let words = HEADER_WORDS * mem::size_of::<usize>();
+    if align > words { align } else { words }
+}
+
+/// # Safety
+/// Range `size` dans le mot précédant `ptr`, qui doit appartenir à la même allocation.
+#[cfg(feature = "header")]
+unsafe fn write_size_header(ptr: *mut u8, size: usize) {
+    (ptr as *mut usize).sub(1).write(size);
+}
+
+/// # Safety
+/// Relit la taille rangée par `write_size_header`.
+#[cfg(feature = "header")]
+unsafe fn read_size_header(ptr: *mut u8) -> usize {
+    (ptr as *const usize).sub(1).read()
+}
+
+/// # Safety
+/// Range l'étiquette `tag` dans le premier mot d'en-tête de l'allocation `ptr`.
+#[cfg(feature = "debug_tags")]
+unsafe fn write_tag_header(ptr: *mut u8, tag: u32) {
+    (ptr as *mut usize).sub(HEADER_WORDS).write(tag as usize);
+}
+
+/// # Safety
+/// Relit l'étiquette rangée par `write_tag_header`.
+#[cfg(feature = "debug_tags")]
+unsafe fn read_tag_header(ptr: *mut u8) -> u32 {
+    (ptr as *const usize).sub(HEADER_WORDS).read() as u32
+}
+
+// Octet de remplissage de la mémoire libérée, pour repérer les utilisations après libération.
+#[cfg(feature = "poison")]
+const POISON: u8 = 0xAB;
+
+/// # Safety
+/// Empoisonne la région libérée `addr..addr + size`, sauf la place de l'en-tête `Block`.
+#[cfg(feature = "poison")]
+unsafe fn poison(addr: usize, size: usize) {
+    let header = mem::size_of::<Block>();
+    if size > header {
+        ptr::write_bytes((addr + header) as *mut u8, POISON, size - header);
+    }
+}
+
+// Un allocateur basé sur une liste chaînée de blocs libres.
+
+// Cet allocateur suit une stratégie simple : trouver un bloc
+// libre qui peut satisfaire une demande d'allocation et le decouper si nécessaire.
+
+/// Nombre de classes de taille : la classe `i` regroupe les blocs de `16 << i` à
+/// `(32 << i) - 1` octets, la dernière recevant tous les grands blocs.
+pub const SIZE_CLASSES: usize = 8;
+
+/// Plus grande taille de bloc gardée dans une case rapide par `with_small_bins`.
+pub const SMALL_BIN_MAX: usize = 128;
+
+// Les cases rapides sont espacées de l'alignement des blocs, multiple de toute taille ajustée.
+const BIN_GRANULE: usize = mem::align_of::<Block>();
+
+/// Nombre de cases rapides : la case `i` garde les blocs d'exactement `i` fois l'alignement
+/// des blocs.
+pub const SMALL_BINS: usize = SMALL_BIN_MAX / BIN_GRANULE + 1;
+
+/// Retourne la case rapide des blocs de `size` octets, ou `None` s'ils vont dans les listes triées.
+fn small_bin(size: usize) -> Option<usize> {
+    (size <= SMALL_BIN_MAX && size.is_multiple_of(BIN_GRANULE)).then_some(size / BIN_GRANULE)
+}
+
+/// Retourne la classe de taille d'un bloc de `size` octets.
+fn size_class(size: usize) -> usize {
+    (size.max(16).ilog2() as usize - 4).min(SIZE_CLASSES - 1)
+}
+
+/// Retourne l'adresse, alignée sur `alignment`, où placer `size` octets dans `start..end`,
+/// ou `Err(())` s'ils n'y tiennent pas. Un calcul qui déborderait `usize` échoue aussi.
+fn fit_allocation(start: usize, end: usize, size: usize, alignment: usize) -> Result<usize, ()> {
+    debug_assert!(alignment.is_power_of_two(), "alignement invalide : {}", alignment);
+
+    let aligned_address = start.checked_add(alignment - 1).ok_or(())? & !(alignment - 1);
+    match aligned_address.checked_add(size) {
+        Some(allocation_end) if allocation_end <= end => Ok(aligned_address),
+        _ => Err(()),
+    }
+}
+
+/// Réduit la région `start..start + size` à la plus grande sous-région dont le début et la
+/// fin sont alignés comme un en-tête de bloc ; la taille retournée est nulle s'il n'y en a pas.
+fn align_region(start: usize, size: usize) -> (usize, usize) {
+    let alignment = mem::align_of::<Block>();
+    let end = start.saturating_add(size) & !(alignment - 1);
+
+    match start.checked_next_multiple_of(alignment) {
+        Some(aligned_start) if aligned_start < end => (aligned_start, end - aligned_start),
+        _ => (start, 0),
+    }
+}
+
+/// # Safety
+/// Détecte un cycle dans la liste commençant à `head` (algorithme du lièvre et de la tortue).
+/// Chaque pointeur `next` non nul de la liste doit désigner un bloc lisible.
+unsafe fn has_cycle(head: *mut Block) -> bool {
+    let mut slow = head;
+    let mut fast = head;
+
+    while !fast.is_null() && !(*fast).next().is_null() {
+        slow = (*slow).next();
+        fast = (*(*fast).next()).next();
+
+        if slow == fast {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Itérateur sur les blocs libres, produisant des paires `(adresse, taille)`
+/// classe par classe, puis par adresse croissante ; les blocs des cases rapides suivent,
+/// case par case.
+pub struct FreeBlockIter<'a> {
+    heads: *const [*mut Block; SIZE_CLASSES],
+    bins: [*mut Block; SMALL_BINS],
+    class: usize,
+    current: *const Block,
+    _allocator: PhantomData<&'a ()>,
+}
+
+impl Iterator for FreeBlockIter<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        // Le contrat de `free_blocks` garantit que les listes ne sont pas modifiées pendant le parcours
+        while self.current.is_null() {
+            if self.class + 1 >= SIZE_CLASSES + SMALL_BINS {
+                return None;
+            }
+
+            self.class += 1;
+            self.current = match self.class.checked_sub(SIZE_CLASSES) {
+                Some(bin) => self.bins[bin],
+                None => unsafe { (*self.heads)[self.class] },
+            };
+        }
+
+        let block = unsafe { &*self.current };
+        self.current = block.next();
+        Some((block.starting_addr(), block.size()))
+    }
+}
+
+/// Itérateur sur la mémoire allouée des régions enregistrées, produisant des paires
+/// `(adresse, taille)` par adresse croissante. Chaque paire couvre l'intervalle entre deux
+/// blocs libres : des allocations contiguës y sont regroupées, avec leurs fins non découpées.
+pub struct AllocIter<'a, const MIN: usize = DEFAULT_MIN_BLOCK_SIZE> {
+    allocator: &'a FreeListAllocator<MIN>,
+    region: usize,
+    cursor: usize,
+}
+
+impl<const MIN: usize> Iterator for AllocIter<'_, MIN> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        // Le contrat de `allocated_blocks` garantit que les listes ne sont pas modifiées pendant le parcours
+        while self.region < self.allocator.region_count.get() {
+            let (start, size) = unsafe { (*self.allocator.regions.get())[self.region] };
+            let end = start + size;
+            self.cursor = self.cursor.max(start);
+
+            if self.cursor >= end {
+                self.region += 1;
+                continue;
+            }
+
+            // Un bloc libre commence ici : on le saute, sinon la mémoire est allouée jusqu'au suivant
+            let next_free = unsafe { self.allocator.next_free_block(self.cursor) };
+            let allocated_end = match next_free {
+                Some((free_start, free_size)) if free_start == self.cursor => {
+                    self.cursor += free_size;
+                    continue;
+                }
+                Some((free_start, _)) => free_start.min(end),
+                None => end,
+            };
+
+            let allocated = (self.cursor, allocated_end - self.cursor);
+            self.cursor = allocated_end;
+            return Some(allocated);
+        }
+
+        None
+    }
+}
+
+/// Stratégie de recherche d'un bloc libre utilisée par `find_block`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Premier bloc assez grand rencontré dans la plus petite classe possible.
+    FirstFit,
+    /// Plus petit bloc assez grand de toutes les listes.
+    BestFit,
+    /// Plus grand bloc disponible, pour garder des restes réutilisables.
+    WorstFit,
+    /// Premier bloc assez grand à partir de l'endroit où la recherche précédente s'est arrêtée.
+    NextFit,
+    /// Plus petit bloc assez grand, comme `BestFit` : le début sauté pour l'alignement et le
+    /// reste totalisent toujours la taille du bloc moins l'allocation. Entre blocs de même
+    /// taille, celui dont le début sauté est le plus court, qui n'aura pas à être redécoupé.
+    BestFitAligned,
+}
+
+/// Raison de l'échec d'une allocation par `try_alloc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocError {
+    /// Aucun bloc libre ne peut satisfaire la requête.
+    OutOfMemory,
+    /// La taille ajustée de la requête dépasse les limites d'un `Layout`.
+    InvalidLayout,
+}
+
+/// Raison du refus d'une libération par `try_dealloc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeallocError {
+    /// Le pointeur n'appartient à aucune région de l'allocateur ou n'est pas aligné comme un bloc.
+    NotOwned,
+    /// Le bloc libéré recoupe un bloc déjà libre.
+    DoubleFree,
+}
+
+/// Raison du refus d'un tas par `try_init`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitError {
+    /// La région ne peut pas contenir l'en-tête d'un bloc.
+    TooSmall,
+    /// Le début de la région n'est pas aligné comme un en-tête de bloc.
+    Misaligned,
+}
+
+/// Raison du refus d'un instantané par `restore`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreError {
+    /// Le tampon est plus court que l'instantané qu'il annonce.
+    Truncated,
+    /// Les régions ou la taille du tas ont changé depuis l'instantané.
+    HeapChanged,
+    /// Un bloc est rangé dans une liste qui n'existe pas.
+    InvalidList,
+    /// Un bloc est mal aligné, trop petit ou déborde de sa région.
+    InvalidBlock,
+    /// Les listes décrites violent un invariant que `validate` vérifie : blocs qui se
+    /// chevauchent ou recouvrent la réserve, liste non triée, bloc hors de sa classe.
+    Inconsistent(ValidationError),
+}
+
+/// Position de l'arène d'allocation linéaire capturée par `mark`, à rétablir par `reset_to`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Mark {
+    cursor: usize,
+}
+
+/// Invariant des listes libres violé, détecté par `validate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Une liste repasse par un bloc déjà visité.
+    Cycle,
+    /// Deux blocs consécutifs d'une liste ne sont pas triés par adresse.
+    Unsorted,
+    /// Deux blocs libres se chevauchent.
+    Overlap,
+    /// Un bloc déborde des régions enregistrées.
+    OutOfRegion,
+    /// Un bloc est trop petit pour contenir son en-tête.
+    TooSmall,
+    /// Un bloc est rangé dans une autre liste que celle de sa classe de taille.
+    WrongSizeClass,
+}
+
+// Motif de la zone rouge de `with_redzone`.
+const REDZONE_PATTERN: u8 = 0xFD;
+
+// Mots d'un instantané de `snapshot` : nombre de blocs, statistiques, taille du tas, curseur
+// next-fit, bloc de secours et nombre de régions, puis début et taille de chaque région, puis
+// adresse, taille, liste et drapeaux de chaque bloc.
+const SNAPSHOT_HEADER_WORDS: usize = 11;
+const SNAPSHOT_REGION_WORDS: usize = 2;
+const SNAPSHOT_BLOCK_WORDS: usize = 4;
+
+/// Taille de ligne de cache par défaut de `with_cache_line_rounding`.
+pub const DEFAULT_CACHE_LINE: usize = 64;
+
+/// Les déplacements de `compact` conservent les adresses modulo cette valeur (au moins) :
+/// les allocations alignées jusqu'à elle le restent.
+pub const COMPACT_ALIGN: usize = 64;
+
+/// Nombre maximal de blocs libres pris en compte par la simulation de `can_allocate_all`.
+pub const SIMULATED_BLOCKS: usize = 64;
+
+/// Nombre maximal de régions (banques mémoire) gérées par un allocateur.
+pub const MAX_REGIONS: usize = 8;
+
+/// Nombre maximal d'étiquettes suivies en même temps par `live_blocks_by_tag`.
+#[cfg(feature = "debug_tags")]
+pub const MAX_TAGS: usize = 16;
+
+/// Instantané des statistiques d'utilisation de l'allocateur.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Octets actuellement alloués (tailles ajustées).
+    pub allocated_bytes: usize,
+    /// Octets présents dans la liste des blocs libres.
+    pub free_bytes: usize,
+    /// Nombre total d'allocations réussies.
+    pub allocation_count: usize,
+    /// Nombre total de libérations acceptées.
+    pub dealloc_count: usize,
+    /// Plus grande valeur atteinte par `allocated_bytes`.
+    pub peak_allocated: usize,
+}
+
+impl AllocStats {
+    const ZERO: AllocStats = AllocStats {
+        allocated_bytes: 0,
+        free_bytes: 0,
+        allocation_count: 0,
+        dealloc_count: 0,
+        peak_allocated: 0,
+    };
+}
+
+/// Fonction recevant le début et la taille d'une région rendue par `set_release_callback`.
+pub type ReleaseCallback = fn(usize, usize);
+
+/// Taille minimale par défaut d'un bloc : celle de son en-tête.
+pub const DEFAULT_MIN_BLOCK_SIZE: usize = mem::size_of::<Block>();
+
+// Allocateur FreeList
+//
+// `MIN` fixe la taille minimale d'un bloc, allocation ou reste de découpage ; elle est
+// relevée à la taille de l'en-tête si besoin.
+pub struct FreeListAllocator<const MIN: usize = DEFAULT_MIN_BLOCK_SIZE> {
+    free_list: Spinlock<[*mut Block; SIZE_CLASSES]>, // Listes libres par classe de taille, triées par adresse ; le verrou protège tout l'état
+    strategy: Strategy,              // Stratégie de recherche des blocs
+    rover: Cell<usize>,              // Adresse à partir de laquelle reprend la recherche next-fit
+    stats: Cell<AllocStats>,         // Statistiques d'utilisation
+    heap_size: Cell<usize>,          // Taille totale des régions confiées à l'allocateur
+    oom_handler: Cell<Option<fn(Layout)>>, // Appelée quand une allocation échoue
+    alloc_fill: Cell<Option<u8>>,    // Octet écrit dans chaque allocation rendue par `alloc`
+    #[cfg(feature = "fault_injection")]
+    forced_failures: Cell<usize>,    // Nombre d'allocations à venir qui échoueront d'office
+    low_watermark: Cell<usize>,      // Seuil de mémoire libre sous lequel `watermark_handler` est appelée
+    watermark_handler: Cell<Option<fn(usize)>>, // Appelée quand la mémoire libre passe sous le seuil
+    watermark_armed: Cell<bool>,     // La fonction du seuil sera appelée au prochain franchissement
+    release_threshold: Cell<usize>,  // Taille au-delà de laquelle une région entièrement libre est rendue
+    release_callback: Cell<Option<ReleaseCallback>>, // Reçoit (début, taille) de chaque région rendue
+    regions: UnsafeCell<[(usize, usize); MAX_REGIONS]>, // Banques mémoire enregistrées (début, taille)
+    region_count: Cell<usize>,       // Nombre d'entrées valides dans `regions`
+    reserve: Cell<(usize, usize)>,   // Bloc de secours hors des listes libres (début, taille)
+    bump: Cell<(usize, usize, usize)>, // Arène d'allocation linéaire (début, curseur, fin), vide si inactive
+    min_align: usize,                // Alignement minimal imposé à toutes les allocations
+    line_rounding: bool,             // Les allocations occupent des lignes de cache entières
+    redzone: usize,                  // Octets gardés intacts en haut du tas confié à `init`
+    redzone_at: Cell<(usize, usize)>, // Zone rouge en place (début, taille)
+    cache_line: usize,               // Taille d'une ligne de cache pour `line_rounding`
+    max_search: Cell<usize>,         // Nombre maximal de blocs examinés par recherche (0 : illimité)
+    nodes_visited: Cell<u64>,        // Blocs libres examinés par toutes les recherches
+    last_shortfall: Cell<usize>,      // Octets manquants à la dernière recherche infructueuse
+    histogram: Cell<[u64; 32]>,       // Requêtes par taille, case `i` pour `2^i..2^(i + 1)` octets
+    #[cfg(feature = "header")]
+    split_threshold: Cell<Option<usize>>, // Marge au-delà de la taille ajustée sous laquelle un reste n'est pas découpé
+    placement_rng: Cell<u64>,         // État du générateur des placements aléatoires (0 : désactivés)
+    remainders_at_head: bool,        // Les restes de découpage vont en tête de leur classe
+    from_top: bool,                  // Les allocations sont prises à la fin des blocs
+    coalesce_retry: bool,            // Une allocation échouée fusionne les blocs libres puis réessaie
+    use_small_bins: bool,            // Les petits blocs libérés vont dans les cases rapides
+    small_bins: Cell<[*mut Block; SMALL_BINS]>, // Piles de blocs libres de taille exacte, sans fusion
+    #[cfg(feature = "debug_tags")]
+    current_tag: Cell<u32>,          // Étiquette donnée aux nouvelles allocations
+    #[cfg(feature = "debug_tags")]
+    tags: Cell<[(u32, usize); MAX_TAGS]>, // Octets alloués encore vivants par étiquette
+}
+
+/// # Safety
+/// Cette implémentation de `GlobalAlloc` doit garantir que :
+/// - `alloc` retourne une région mémoire correctement alignée.
+/// - `dealloc` libère uniquement les blocs préalablement alloués par cet allocateur.
+/// - Les opérations de modification sur la liste des blocs libres sont protégées par le verrou de `free_list`.
+unsafe impl<const MIN: usize> Sync for FreeListAllocator<MIN> {}
+
+impl<const MIN: usize> Default for FreeListAllocator<MIN> {
+    fn default() -> Self {
+        Self::configured(Strategy::FirstFit)
+    }
+}
+
+unsafe impl<const MIN: usize> GlobalAlloc for FreeListAllocator<MIN> {
+    /// # Safety
+    /// Cette méthode est marquée `unsafe` car elle effectue des opérations de bas niveau
+    /// pour allouer de la mémoire brute. L'appelant doit garantir que :
+    /// - Le `Layout` fourni est valide.
+    /// - La mémoire retournée est utilisée conformément aux règles du `Layout`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.try_alloc(layout).map_or(null_mut(), NonNull::as_ptr)
+    }
+
+    /// # Safety
+    /// Mêmes exigences que `alloc`. La mise à zéro est évitée si le bloc n'a jamais été écrit.
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let (ptr, dirty) = {
+            let _guard = self.free_list.lock();
+            self.alloc_block(layout)
+        };
+
+        self.check_watermark();
+        if ptr.is_null() {
+            self.notify_oom(layout);
+        } else {
+            // Un bloc propre ne contient que les octets de son ancien en-tête
+            let len = if dirty { layout.size() } else { layout.size().min(mem::size_of::<Block>()) };
+            ptr::write_bytes(ptr, 0, len);
+        }
+        ptr
+    }
+
+    /// # Safety
+    /// Cette méthode est `unsafe` car elle manipule directement les pointeurs
+    /// et nécessite que l'appelant garantisse :
+    /// - Que `ptr` pointe vers une région valide allouée par cet allocateur.
+    /// - Que la taille et l'alignement fournis dans `Layout` correspondent à ceux utilisés lors de l'allocation.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = self.try_dealloc(ptr, layout);
+        self.release_region_of(ptr);
+        self.check_watermark();
+    }
+
+    /// # Safety
+    /// Mêmes exigences que `GlobalAlloc::realloc` : `ptr` doit provenir de cet allocateur
+    /// avec `layout`, et `new_size` arrondi à l'alignement ne doit pas dépasser `isize::MAX`.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+
+        // Rien à copier ni à libérer depuis une allocation de taille nulle
+        if layout.size() == 0 {
+            return self.alloc(new_layout);
+        }
+
+        // Une allocation de l'arène est recopiée à sa suite ; l'ancienne place attend `reset_to`
+        let in_bump = {
+            let _guard = self.free_list.lock();
+            self.in_bump(ptr)
+        };
+        if in_bump {
+            let new_ptr = self.alloc(new_layout);
+            if !new_ptr.is_null() {
+                ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            }
+            return new_ptr;
+        }
+
+        let (Some((addr, old_size)), Some((new_adjusted_size, _))) =
+            (self.block_of(ptr, layout), self.adjust_layout(new_layout))
+        else {
+            return null_mut();
+        };
+
+        #[cfg(feature = "canaries")]
+        check_canary(ptr, layout.size());
+
+        {
+            let _guard = self.free_list.lock();
+
+            // Réduction sur place : la fin libérée retourne dans la liste
+            if new_adjusted_size <= old_size {
+                let released = old_size - new_adjusted_size;
+
+                #[cfg(feature = "poison")]
+                poison(addr + new_adjusted_size, released);
+
+                self.update_stats(|stats| stats.allocated_bytes = stats.allocated_bytes.saturating_sub(released));
+                self.insert_region(addr + new_adjusted_size, released, true);
+
+                #[cfg(feature = "debug_tags")]
+                self.account_tag(ptr, 0, released);
+
+                #[cfg(feature = "header")]
+                write_size_header(ptr, new_adjusted_size);
+
+                #[cfg(feature = "canaries")]
+                write_canary(ptr, new_size);
+                return ptr;
+            }
+
+            // Agrandissement sur place si le bloc libre qui suit suffit et appartient à la même région
+            let neighbor = if self.same_region(addr, addr + old_size) {
+                self.take_block_at(addr + old_size, new_adjusted_size - old_size)
+            } else {
+                null_mut()
+            };
+            if !neighbor.is_null() {
+                let total_size = old_size + (*neighbor).size();
+                self.record_allocation(new_adjusted_size - old_size, false);
+                self.insert_region(addr + new_adjusted_size, total_size - new_adjusted_size, true);
+
+                #[cfg(feature = "debug_tags")]
+                self.account_tag(ptr, new_adjusted_size - old_size, 0);
+
+                #[cfg(feature = "header")]
+                write_size_header(ptr, new_adjusted_size);
+
+                #[cfg(feature = "canaries")]
+                write_canary(ptr, new_size);
+                return ptr;
+            }
+        }
+
+        // Sinon : nouvelle allocation, copie puis libération de l'ancien bloc
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
+impl<const MIN: usize> Owns for FreeListAllocator<MIN> {
+    fn owns(&self, ptr: *const u8) -> bool {
+        self.contains(ptr)
+    }
+}
+
+/// Allocateur à portée limitée, par exemple pour `Vec::new_in(&allocator)` (nightly).
+#[cfg(feature = "allocator_api")]
+unsafe impl<const MIN: usize> core::alloc::Allocator for &FreeListAllocator<MIN> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+        FreeListAllocator::allocate(*self, layout).ok_or(core::alloc::AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.dealloc(ptr.as_ptr(), layout);
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+        // `realloc` conserve l'alignement d'origine : sinon, nouvelle allocation et copie
+        let new_ptr = if new_layout.align() == old_layout.align() {
+            FreeListAllocator::grow_zeroed(*self, ptr.as_ptr(), old_layout, new_layout.size())
+        } else {
+            let new_ptr = self.alloc(new_layout);
+            if !new_ptr.is_null() {
+                ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr, old_layout.size());
+                ptr::write_bytes(new_ptr.add(old_layout.size()), 0, new_layout.size() - old_layout.size());
+                self.dealloc(ptr.as_ptr(), old_layout);
+            }
+            new_ptr
+        };
+
+        NonNull::new(new_ptr)
+            .map(|ptr| NonNull::slice_from_raw_parts(ptr, new_layout.size()))
+            .ok_or(core::alloc::AllocError)
+    }
+}
+
+impl FreeListAllocator {
+    /// Crée un allocateur vide utilisant la stratégie first-fit.
+    pub const fn new() -> Self {
+        Self::with_strategy(Strategy::FirstFit)
+    }
+
+    /// Crée un allocateur vide utilisant la stratégie de recherche donnée.
+    pub const fn with_strategy(strategy: Strategy) -> Self {
+        Self::configured(strategy)
+    }
+}
+
+impl<const MIN: usize> FreeListAllocator<MIN> {
+    /// Taille minimale effective d'un bloc : `MIN`, au moins la taille de l'en-tête,
+    /// arrondie à l'alignement des blocs.
+    pub const MIN_BLOCK_SIZE: usize = {
+        let min = if MIN > mem::size_of::<Block>() { MIN } else { mem::size_of::<Block>() };
+        let alignment = mem::align_of::<Block>();
+        (min + alignment - 1) & !(alignment - 1)
+    };
+
+    /// # Safety
+    /// Alloue un bloc pour `layout` et indique s'il a pu être écrit depuis l'initialisation.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn alloc_block(&self, layout: Layout) -> (*mut u8, bool) {
+        // Taille nulle : pointeur non nul et aligné, sans consommer de mémoire
+        if layout.size() == 0 {
+            return (layout.align() as *mut u8, false);
+        }
+
+        // Une allocation de taille nulle ne pouvant échouer, elle ne consomme pas d'échec forcé
+        #[cfg(feature = "fault_injection")]
+        if self.forced_failures.get() > 0 {
+            self.forced_failures.set(self.forced_failures.get() - 1);
+            return (null_mut(), false);
+        }
+
+        let mut histogram = self.histogram.get();
+        histogram[(layout.size().ilog2() as usize).min(31)] += 1;
+        self.histogram.set(histogram);
+
+        // En allocation linéaire, l'arène seule sert les requêtes
+        if self.bump_active() {
+            return (self.bump_alloc(layout), true);
+        }
+
+        let Some((adjusted_size, alignment)) = self.adjust_layout(layout) else {
+            return (null_mut(), false);
+        };
+
+        // Inutile de parcourir la liste pour une requête plus grande que le tas entier
+        if adjusted_size > self.heap_size.get() {
+            self.record_shortfall(adjusted_size, alignment);
+            return (null_mut(), false);
+        }
+
+        // Une case rapide de la bonne taille sert la requête sans parcours ; sinon on passe
+        // par `find_block` pour obtenir une adresse correctement alignée
+        let mut found = self.pop_small_bin(adjusted_size, alignment);
+        if found.is_none() {
+            found = self.find_block_locked(adjusted_size, alignment);
+        }
+        if found.is_none() && self.flush_small_bins() {
+            found = self.find_block_locked(adjusted_size, alignment);
+        }
+        if found.is_none() && self.coalesce_retry {
+            self.coalesce_locked();
+            found = self.find_block_locked(adjusted_size, alignment);
+        }
+
+        match found {
+            Some((block, allocation_address)) => {
+                let allocation_end = allocation_address + adjusted_size;
+                let remaining = (*block).finishing_addr() - allocation_end;
+                let dirty = (*block).dirty;
+
+                // Découpage : la fin inutilisée du bloc retourne dans la liste. Le bloc suivant
+                // n'étant pas libre, le reste n'a aucun voisin avec qui fusionner en tête de liste.
+                // Une fin trop petite pour former un bloc reste dans l'allocation, et revient avec
+                // elle à sa libération
+                let split = remaining >= Self::MIN_BLOCK_SIZE;
+
+                // Un reste qui ne dépasse pas la taille ajustée plus le seuil de découpage est
+                // laissé à l'allocation ; l'en-tête en garde la taille pour que la libération le rende
+                #[cfg(feature = "header")]
+                let unsplit = self.split_threshold.get().is_some_and(|threshold| remaining <= adjusted_size.saturating_add(threshold));
+                #[cfg(feature = "header")]
+                let (split, adjusted_size) = if split && unsplit {
+                    (false, adjusted_size + remaining)
+                } else {
+                    (split, adjusted_size)
+                };
+
+                if split {
+                    if self.remainders_at_head {
+                        self.push_front(allocation_end, remaining, dirty);
+                    } else {
+                        self.insert_region(allocation_end, remaining, dirty);
+                    }
+                }
+
+                self.record_allocation(adjusted_size, true);
+
+                // Les données suivent les mots d'en-tête
+                #[cfg(any(feature = "header", feature = "debug_tags"))]
+                let allocation_address = allocation_address + header_offset(layout.align().max(self.min_align));
+
+                #[cfg(feature = "header")]
+                write_size_header(allocation_address as *mut u8, adjusted_size);
+
+                #[cfg(feature = "debug_tags")]
+                {
+                    write_tag_header(allocation_address as *mut u8, self.current_tag.get());
+                    self.account_tag(allocation_address as *mut u8, adjusted_size, 0);
+                }
+
+                #[cfg(feature = "canaries")]
+                write_canary(allocation_address as *mut u8, layout.size());
+
+                (allocation_address as *mut u8, dirty)
+            }
+            None => (null_mut(), false),
+        }
+    }
+
+    /// # Safety
+    /// Comme `alloc`, mais indique la raison d'un échec au lieu de retourner un pointeur nul.
+    pub unsafe fn try_alloc(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        if self.adjust_layout(layout).is_none() {
+            return Err(AllocError::InvalidLayout);
+        }
+
+        let (ptr, fill) = {
+            let _guard = self.free_list.lock();
+            (self.alloc_block(layout).0, self.alloc_fill.get())
+        };
+        self.check_watermark();
+
+        match NonNull::new(ptr) {
+            Some(ptr) => {
+                if let Some(byte) = fill {
+                    ptr::write_bytes(ptr.as_ptr(), byte, layout.size());
+                }
+                Ok(ptr)
+            }
+            None => {
+                self.notify_oom(layout);
+                Err(AllocError::OutOfMemory)
+            }
+        }
+    }
+
+    /// # Safety
+    /// Comme `dealloc`, mais indique si la libération a été refusée. Les vérifications
+    /// n'ont lieu qu'avec `debug_checks` : sans elles, toute libération réussit.
+    pub unsafe fn try_dealloc(&self, ptr: *mut u8, layout: Layout) -> Result<(), DeallocError> {
+        // Le pointeur d'une allocation de taille nulle ne correspond à aucun bloc
+        if layout.size() == 0 {
+            return Ok(());
+        }
+
+        // Les allocations de l'arène ne sont libérées que par `reset_to` ou `end_bump`
+        {
+            let _guard = self.free_list.lock();
+            if self.in_bump(ptr) {
+                return Ok(());
+            }
+        }
+
+        // Un pointeur étranger ou mal aligné corromprait les listes libres : il est refusé.
+        // La mémoire confiée par `insert_free_region` n'étant dans aucune région, l'appartenance
+        // n'est vérifiable que si des régions ont été enregistrées.
+        #[cfg(feature = "debug_checks")]
+        {
+            let registered = {
+                let _guard = self.free_list.lock();
+                self.region_count.get() > 0
+            };
+            if (registered && !self.contains(ptr)) || !(ptr as usize).is_multiple_of(mem::align_of::<Block>()) {
+                return Err(DeallocError::NotOwned);
+            }
+        }
+
+        #[cfg(feature = "canaries")]
+        check_canary(ptr, layout.size());
+
+        // Un layout impossible à ajuster n'a pas pu être alloué par cet allocateur
+        let Some((addr, adjusted_size)) = self.block_of(ptr, layout) else {
+            return Err(DeallocError::NotOwned);
+        };
+
+        // Un bloc qui recoupe un bloc libre, cases rapides comprises, a déjà été libéré
+        #[cfg(feature = "debug_checks")]
+        {
+            let _guard = self.free_list.lock();
+            let end = addr + adjusted_size;
+            let mut overlaps = false;
+            self.for_each_listed_block(|_, block| {
+                overlaps |= (*block).starting_addr() < end && addr < (*block).finishing_addr();
+            });
+            if overlaps {
+                return Err(DeallocError::DoubleFree);
+            }
+        }
+
+        #[cfg(feature = "poison")]
+        poison(addr, adjusted_size);
+
+        let _guard = self.free_list.lock();
+        self.update_stats(|stats| {
+            stats.allocated_bytes = stats.allocated_bytes.saturating_sub(adjusted_size);
+            stats.dealloc_count += 1;
+        });
+
+        #[cfg(feature = "debug_tags")]
+        self.account_tag(ptr, 0, adjusted_size);
+
+        let (addr, size) = self.reclaim_slack(addr, adjusted_size);
+        if !self.push_small_bin(addr, size) {
+            self.insert_region(addr, size, true);
+        }
+        Ok(())
+    }
+
+    /// # Safety
+    /// Comme `realloc` vers une taille plus grande, mais les octets ajoutés
+    /// `old_layout.size()..new_size` sont mis à zéro ; seuls eux le sont, le contenu
+    /// d'origine étant conservé, que le bloc grandisse sur place ou soit déplacé.
+    pub unsafe fn grow_zeroed(&self, ptr: *mut u8, old_layout: Layout, new_size: usize) -> *mut u8 {
+        debug_assert!(new_size >= old_layout.size(), "grow_zeroed ne peut pas réduire un bloc");
+
+        let new_ptr = self.realloc(ptr, old_layout, new_size);
+        if !new_ptr.is_null() {
+            ptr::write_bytes(new_ptr.add(old_layout.size()), 0, new_size - old_layout.size());
+        }
+        new_ptr
+    }
+
+    /// # Safety
+    /// Comme `realloc`, mais le bloc retourné est aligné sur `new_align` : il faut ensuite le
+    /// libérer avec `Layout::from_size_align(new_size, new_align)`. Si le bloc ne peut pas servir
+    /// tel quel sous le nouvel alignement, un nouveau bloc est alloué, le contenu recopié et
+    /// l'ancien libéré. Retourne un pointeur nul si le nouveau layout est invalide ou en cas
+    /// d'échec, l'ancien bloc restant alors valide.
+    pub unsafe fn realloc_aligned(&self, ptr: *mut u8, old: Layout, new_size: usize, new_align: usize) -> *mut u8 {
+        if new_align == old.align() {
+            return self.realloc(ptr, old, new_size);
+        }
+        let (Ok(new_layout), Ok(kept_layout)) =
+            (Layout::from_size_align(new_size, new_align), Layout::from_size_align(new_size, old.align()))
+        else {
+            return null_mut();
+        };
+
+        // Sur place seulement si le bloc est déjà aligné, ne grandit pas et garde la même taille
+        // ajustée sous les deux alignements ; les en-têtes dépendent de l'alignement
+        let adjusted_size = |layout| self.adjust_layout(layout).map(|(size, _)| size);
+        let in_place = !cfg!(any(feature = "header", feature = "debug_tags"))
+            && old.size() != 0
+            && new_size != 0
+            && (ptr as usize).is_multiple_of(new_align)
+            && adjusted_size(new_layout).is_some()
+            && adjusted_size(new_layout) == adjusted_size(kept_layout)
+            && adjusted_size(new_layout) <= adjusted_size(old);
+        if in_place {
+            return self.realloc(ptr, old, new_size);
+        }
+
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, old.size().min(new_size));
+            self.dealloc(ptr, old);
+        }
+        new_ptr
+    }
+
+    /// # Safety
+    /// Retourne le nombre d'octets utilisables à partir de `ptr`, alloué avec `layout` : la
+    /// taille arrondie du bloc, y compris une fin trop petite pour avoir été découpée. L'appelant
+    /// peut s'en servir sans réallouer, puis libérer avec le même `layout`. Avec des canaris,
+    /// seule la taille demandée est utilisable.
+    pub unsafe fn usable_size(&self, ptr: *mut u8, layout: Layout) -> usize {
+        if layout.size() == 0 || cfg!(feature = "canaries") {
+            return layout.size();
+        }
+        let Some((addr, size)) = self.block_of(ptr, layout) else {
+            return 0;
+        };
+
+        let _guard = self.free_list.lock();
+        if self.in_bump(ptr) {
+            return size;
+        }
+        let (start, size) = self.reclaim_slack(addr, size);
+        start + size - ptr as usize
+    }
+
+    /// Alloue `layout` et retourne toute la place utilisable, qui peut dépasser la taille
+    /// demandée après arrondi : l'appelant peut se servir de l'excédent.
+    pub fn allocate(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        let ptr = unsafe { self.try_alloc(layout) }.ok()?;
+        let (adjusted_size, _) = self.adjust_layout(layout)?;
+
+        // Tout le bloc ajusté est utilisable, sauf si des octets de contrôle entourent les données
+        let len = if layout.size() == 0 || cfg!(any(feature = "canaries", feature = "header", feature = "debug_tags")) {
+            layout.size()
+        } else {
+            adjusted_size
+        };
+        Some(NonNull::slice_from_raw_parts(ptr, len))
+    }
+
+    /// Comptabilise `size` octets nouvellement alloués. L'appelant doit détenir le verrou.
+    fn record_allocation(&self, size: usize, new_allocation: bool) {
+        self.update_stats(|stats| {
+            stats.allocated_bytes += size;
+            stats.allocation_count += new_allocation as usize;
+            stats.peak_allocated = stats.peak_allocated.max(stats.allocated_bytes);
+        });
+    }
+
+    /// Modifie les statistiques. L'appelant doit détenir le verrou.
+    fn update_stats(&self, f: impl FnOnce(&mut AllocStats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+
+    /// # Safety
+    /// Met de côté un bloc d'au moins `size` octets que `alloc` ne distribuera jamais, pour
+    /// laisser de quoi travailler à un gestionnaire d'échec. Une réserve précédente est d'abord
+    /// rendue au tas ; `size == 0` supprime la réserve. Si aucun bloc ne convient, il n'y a
+    /// pas de réserve.
+    pub unsafe fn set_reserve(&self, size: usize) {
+        let _guard = self.free_list.lock();
+        self.release_reserve();
+
+        if let Some(reserve) = self.take_region(size) {
+            self.reserve.set(reserve);
+        }
+    }
+
+    /// # Safety
+    /// Retire des listes libres un bloc d'au moins `size` octets, hors statistiques, et
+    /// retourne son début et sa taille ; `None` si `size` est nul ou qu'aucun bloc ne
+    /// convient. L'appelant doit détenir le verrou.
+    unsafe fn take_region(&self, size: usize) -> Option<(usize, usize)> {
+        let alignment = mem::align_of::<Block>();
+        let size = size.checked_add(alignment - 1)?;
+        if size < alignment {
+            return None;
+        }
+        let size = (size & !(alignment - 1)).max(Self::MIN_BLOCK_SIZE);
+
+        let (block, start) = self.find_block_locked(size, alignment)?;
+        let mut end = (*block).finishing_addr();
+
+        // Comme pour une allocation, la fin du bloc retourne dans les listes
+        if end - (start + size) >= Self::MIN_BLOCK_SIZE {
+            self.insert_region(start + size, end - (start + size), (*block).dirty);
+            end = start + size;
+        }
+        Some((start, end - start))
+    }
+
+    /// # Safety
+    /// Passe en allocation linéaire : une arène d'au moins `size` octets est retirée des
+    /// listes libres et toutes les allocations suivantes y sont prises à la suite, sans
+    /// en-tête, jusqu'à `end_bump`. Les libérations dans l'arène sont ignorées ; `mark` et
+    /// `reset_to` libèrent en bloc. Retourne `false` si aucun bloc ne convient ou si une
+    /// arène est déjà active.
+    pub unsafe fn begin_bump(&self, size: usize) -> bool {
+        let _guard = self.free_list.lock();
+        if self.bump_active() {
+            return false;
+        }
+
+        match self.take_region(size) {
+            Some((start, size)) => {
+                self.bump.set((start, start, start + size));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// # Safety
+    /// Quitte l'allocation linéaire et rend toute l'arène aux listes libres. L'appelant
+    /// garantit qu'aucune allocation faite dans l'arène ne sera plus utilisée.
+    pub unsafe fn end_bump(&self) {
+        let _guard = self.free_list.lock();
+        let (start, _, end) = self.bump.replace((0, 0, 0));
+        if end > start {
+            self.insert_region(start, end - start, true);
+        }
+    }
+
+    /// Capture la position courante de l'arène de `begin_bump`.
+    pub fn mark(&self) -> Mark {
+        let _guard = self.free_list.lock();
+        Mark { cursor: self.bump.get().1 }
+    }
+
+    /// # Safety
+    /// Ramène l'arène à la position `m`, ce qui libère toutes les allocations faites depuis :
+    /// l'appelant garantit qu'aucune ne sera plus utilisée. Sans effet si `m` ne désigne pas
+    /// une position antérieure de l'arène active.
+    pub unsafe fn reset_to(&self, m: Mark) {
+        let _guard = self.free_list.lock();
+        let (start, cursor, end) = self.bump.get();
+
+        if self.bump_active() && m.cursor >= start && m.cursor <= cursor {
+            self.bump.set((start, m.cursor, end));
+        }
+    }
+
+    /// Indique si une arène de `begin_bump` est active. L'appelant doit détenir le verrou.
+    fn bump_active(&self) -> bool {
+        let (start, _, end) = self.bump.get();
+        end > start
+    }
+
+    /// Indique si `ptr` a été pris dans l'arène active. L'appelant doit détenir le verrou.
+    fn in_bump(&self, ptr: *const u8) -> bool {
+        let (start, _, end) = self.bump.get();
+        (start..end).contains(&(ptr as usize))
+    }
+
+    /// Prend `layout`, ajusté comme pour un bloc, à la suite de l'arène active, ou retourne
+    /// un pointeur nul si elle est épuisée. L'appelant doit détenir le verrou.
+    fn bump_alloc(&self, layout: Layout) -> *mut u8 {
+        let (start, cursor, end) = self.bump.get();
+        let Some((size, alignment)) = self.adjust_layout(layout) else {
+            return null_mut();
+        };
+
+        let fits = cursor
+            .checked_next_multiple_of(alignment)
+            .and_then(|address| Some((address, address.checked_add(size)?)))
+            .filter(|&(_, allocation_end)| allocation_end <= end);
+        match fits {
+            Some((address, allocation_end)) => {
+                self.bump.set((start, allocation_end, end));
+                address as *mut u8
+            }
+            None => null_mut(),
+        }
+    }
+
+    /// # Safety
+    /// Allocation de dernier recours, réservée aux chemins d'échec : la réserve de `set_reserve`
+    /// est rendue au tas avant d'allouer `layout` normalement. Il faut rappeler `set_reserve`
+    /// pour reconstituer une réserve.
+    pub unsafe fn alloc_from_reserve(&self, layout: Layout) -> *mut u8 {
+        let _guard = self.free_list.lock();
+        self.release_reserve();
+        self.alloc_block(layout).0
+    }
+
+    /// # Safety
+    /// Rend la réserve éventuelle aux listes libres. L'appelant doit détenir le verrou.
+    unsafe fn release_reserve(&self) {
+        let (start, size) = self.reserve.replace((0, 0));
+        if size > 0 {
+            self.insert_region(start, size, true);
+        }
+    }
+
+    /// Installe une fonction appelée avec le `Layout` de chaque allocation qui échoue,
+    /// par exemple pour journaliser l'erreur ou déclencher une récupération de mémoire.
+    pub fn set_oom_handler(&self, f: fn(Layout)) {
+        let _guard = self.free_list.lock();
+        self.oom_handler.set(Some(f));
+    }
+
+    /// Fait échouer les `n` prochaines allocations, quelle que soit la mémoire libre, puis
+    /// laisse les suivantes reprendre normalement. Les échecs suivent le chemin habituel,
+    /// fonction de `set_oom_handler` comprise ; un nouvel appel remplace le compte en cours.
+    #[cfg(feature = "fault_injection")]
+    pub fn fail_next_n(&self, n: usize) {
+        let _guard = self.free_list.lock();
+        self.forced_failures.set(n);
+    }
+
+    /// Fait remplir par `alloc` chaque allocation de l'octet `byte`, par exemple `0xCC`, pour
+    /// repérer les lectures de mémoire non initialisée ; `None` (valeur initiale) désactive
+    /// le remplissage. `alloc_zeroed` continue de rendre une mémoire à zéro.
+    pub fn set_alloc_fill(&self, byte: Option<u8>) {
+        let _guard = self.free_list.lock();
+        self.alloc_fill.set(byte);
+    }
+
+    /// Installe une fonction appelée avec le nombre d'octets libres quand une allocation fait
+    /// passer la mémoire libre sous `bytes`, pour commencer à récupérer de la mémoire avant
+    /// l'échec. Elle n'est rappelée qu'après que la mémoire libre est remontée au seuil.
+    pub fn set_low_watermark(&self, bytes: usize, f: fn(usize)) {
+        let _guard = self.free_list.lock();
+        self.low_watermark.set(bytes);
+        self.watermark_handler.set(Some(f));
+        self.watermark_armed.set(self.stats.get().free_bytes >= bytes);
+    }
+
+    /// Appelle la fonction de `set_low_watermark` si la mémoire libre vient de passer sous le
+    /// seuil, ou réarme le seuil si elle est remontée. Le verrou ne doit pas être détenu.
+    fn check_watermark(&self) {
+        let (free, handler) = {
+            let _guard = self.free_list.lock();
+            let free = self.stats.get().free_bytes;
+
+            if free >= self.low_watermark.get() {
+                self.watermark_armed.set(true);
+                return;
+            }
+            if !self.watermark_armed.replace(false) {
+                return;
+            }
+            (free, self.watermark_handler.get())
+        };
+
+        if let Some(handler) = handler {
+            handler(free);
+        }
+    }
+
+    /// Installe une fonction appelée avec le début et la taille de chaque région de plus de
+    /// `threshold` octets que la libération d'un bloc laisse entièrement libre. La région est
+    /// alors retirée des listes libres et de la taille du tas, par exemple pour être rendue à un
+    /// allocateur de pages : l'allocateur n'y touche plus.
+    pub fn set_release_callback(&self, threshold: usize, f: ReleaseCallback) {
+        let _guard = self.free_list.lock();
+        self.release_threshold.set(threshold);
+        self.release_callback.set(Some(f));
+    }
+
+    /// Rend par la fonction de `set_release_callback` la région contenant `ptr` si elle ne
+    /// forme plus qu'un seul bloc libre assez grand. Le verrou ne doit pas être détenu.
+    fn release_region_of(&self, ptr: *const u8) {
+        let (released, callback) = {
+            let _guard = self.free_list.lock();
+            let callback = self.release_callback.get();
+            let Some(i) = self.region_of(ptr as usize).filter(|_| callback.is_some()) else {
+                return;
+            };
+
+            let (start, size) = unsafe { (*self.regions.get())[i] };
+            if size <= self.release_threshold.get() {
+                return;
+            }
+            let Some((class, previous_block, block)) =
+                (unsafe { self.find_free_block(|block| block.starting_addr() == start && block.size() == size) })
+            else {
+                return;
+            };
+
+            // Une région de taille nulle ne contient plus aucune adresse
+            unsafe {
+                self.unlink(class, previous_block, block);
+                (*self.regions.get())[i] = (start, 0);
+            }
+            self.heap_size.set(self.heap_size.get() - size);
+            ((start, size), callback)
+        };
+
+        if let Some(callback) = callback {
+            callback(released.0, released.1);
+        }
+    }
+
+    /// Appelle la fonction de `set_oom_handler`. Le verrou ne doit pas être détenu,
+    /// la fonction pouvant elle-même libérer de la mémoire.
+    fn notify_oom(&self, layout: Layout) {
+        let handler = {
+            let _guard = self.free_list.lock();
+            self.oom_handler.get()
+        };
+
+        if let Some(handler) = handler {
+            handler(layout);
+        }
+    }
+
+    /// Exécute `f` en étiquetant `tag` toutes les allocations faites entre-temps, puis rétablit
+    /// l'étiquette précédente (0 par défaut). Sans variables locales aux fils en `no_std`,
+    /// l'étiquette courante est commune à tout l'allocateur.
+    #[cfg(feature = "debug_tags")]
+    pub fn with_tag<R>(&self, tag: u32, f: impl FnOnce() -> R) -> R {
+        let previous = {
+            let _guard = self.free_list.lock();
+            self.current_tag.replace(tag)
+        };
+
+        let result = f();
+
+        let _guard = self.free_list.lock();
+        self.current_tag.set(previous);
+        result
+    }
+
+    /// Retourne, pour chaque étiquette ayant des allocations vivantes, le nombre d'octets
+    /// (tailles ajustées) encore alloués. Les entrées à 0 octet sont inutilisées ; au-delà de
+    /// `MAX_TAGS` étiquettes simultanées, les nouvelles ne sont pas comptées.
+    #[cfg(feature = "debug_tags")]
+    pub fn live_blocks_by_tag(&self) -> [(u32, usize); MAX_TAGS] {
+        let _guard = self.free_list.lock();
+        self.tags.get()
+    }
+
+    /// # Safety
+    /// Ajoute `added` puis retire `removed` octets au compte de l'étiquette de l'allocation
+    /// `ptr`. L'appelant doit détenir le verrou de `free_list`.
+    #[cfg(feature = "debug_tags")]
+    unsafe fn account_tag(&self, ptr: *mut u8, added: usize, removed: usize) {
+        let tag = read_tag_header(ptr);
+        let mut tags = self.tags.get();
+
+        let slot = tags
+            .iter()
+            .position(|&(t, bytes)| t == tag && bytes > 0)
+            .or_else(|| tags.iter().position(|&(_, bytes)| bytes == 0));
+        if let Some(i) = slot {
+            tags[i] = (tag, (tags[i].1 + added).saturating_sub(removed));
+            self.tags.set(tags);
+        }
+    }
+
+    /// Limite chaque recherche de bloc à `n` blocs libres examinés, pour borner la durée d'une
+    /// allocation : passé cette limite, la recherche s'arrête sur le meilleur bloc déjà trouvé
+    /// ou échoue, même si un bloc plus loin aurait convenu. `0` supprime la limite.
+    pub fn set_max_search(&self, n: usize) {
+        let _guard = self.free_list.lock();
+        self.max_search.set(n);
+    }
+
+    /// Ne découpe plus un bloc que si son reste dépasse la taille ajustée de l'allocation plus
+    /// `threshold` octets : sinon le bloc entier est donné à l'allocation, ce qui évite de
+    /// multiplier les petits blocs libres au prix d'octets perdus jusqu'à la libération. Sans
+    /// appel, un bloc est découpé dès que son reste forme un bloc.
+    ///
+    /// Disponible seulement avec la fonctionnalité `header` : la libération doit retrouver la
+    /// taille réelle du bloc, que seul l'en-tête de taille conserve. Sans lui, le reste ne
+    /// pourrait pas être distingué d'une allocation voisine et serait perdu.
+    #[cfg(feature = "header")]
+    pub fn set_split_threshold(&self, threshold: usize) {
+        let _guard = self.free_list.lock();
+        self.split_threshold.set(Some(threshold));
+    }
+
+    /// Place chaque allocation à une position pseudo-aléatoire du bloc choisi plutôt qu'à
+    /// sa première adresse alignée, pour rendre les adresses retournées difficiles à
+    /// prévoir. Le début et la fin du bloc laissés de part et d'autre retournent dans les
+    /// listes. La suite des positions ne dépend que de `seed` ; `0` désactive le tirage.
+    pub fn set_placement_seed(&self, seed: u64) {
+        let _guard = self.free_list.lock();
+        self.placement_rng.set(seed);
+    }
+
+    /// Retourne le nombre total de blocs libres examinés par les recherches de bloc depuis
+    /// la création de l'allocateur : une mesure du coût des stratégies indépendante du matériel.
+    pub fn nodes_visited(&self) -> u64 {
+        let _guard = self.free_list.lock();
+        self.nodes_visited.get()
+    }
+
+    /// Retourne le nombre de requêtes d'allocation reçues par taille demandée : la case `i`
+    /// compte les tailles de `2^i` à `2^(i + 1) - 1` octets, la dernière toutes les plus
+    /// grandes. Les requêtes de taille nulle ne sont pas comptées.
+    pub fn size_histogram(&self) -> [u64; 32] {
+        let _guard = self.free_list.lock();
+        self.histogram.get()
+    }
+
+    /// Retourne le nombre d'octets qui auraient manqué au bloc libre le plus proche de
+    /// convenir lors de la dernière allocation échouée, alignement compris : de quoi
+    /// dimensionner un tas. Vaut `0` tant qu'aucune allocation n'a échoué.
+    pub fn last_alloc_shortfall(&self) -> usize {
+        let _guard = self.free_list.lock();
+        self.last_shortfall.get()
+    }
+
+    /// Indique si `ptr` se trouve dans l'une des régions enregistrées par `init`, `add_region`
+    /// ou `extend` ; la mémoire confiée par `insert_free_region` n'appartient à aucune.
+    pub fn contains(&self, ptr: *const u8) -> bool {
+        let _guard = self.free_list.lock();
+        self.region_of(ptr as usize).is_some()
+    }
+
+    /// Retourne la taille maximale qu'une allocation pourrait atteindre, soit la taille du tas.
+    pub fn max_possible_alloc(&self) -> usize {
+        let _guard = self.free_list.lock();
+        self.heap_size.get()
+    }
+
+    /// Retourne un instantané des statistiques d'utilisation.
+    pub fn stats(&self) -> AllocStats {
+        let _guard = self.free_list.lock();
+        self.stats.get()
+    }
+
+    /// Retourne le nombre d'allocations pas encore libérées, `allocation_count - dealloc_count` :
+    /// une valeur non nulle en fin de programme ou de test signale une fuite.
+    pub fn outstanding_allocations(&self) -> usize {
+        let stats = self.stats();
+        stats.allocation_count.saturating_sub(stats.dealloc_count)
+    }
+
+    /// Retourne le plus grand total d'octets alloués simultanément (tailles ajustées),
+    /// pour dimensionner le tas.
+    pub fn peak_used(&self) -> usize {
+        self.stats().peak_allocated
+    }
+
+    /// # Safety
+    /// Parcourt les listes des blocs libres, cases rapides comprises. L'appelant doit garantir
+    /// qu'aucune allocation ni libération n'a lieu tant que l'itérateur est utilisé.
+    pub unsafe fn free_blocks(&self) -> FreeBlockIter<'_> {
+        let heads = self.free_list.get();
+
+        FreeBlockIter {
+            heads,
+            bins: self.small_bins.get(),
+            class: 0,
+            current: (*heads)[0],
+            _allocator: PhantomData,
+        }
+    }
+
+    /// # Safety
+    /// Parcourt la mémoire allouée des régions enregistrées par `init`, `add_region` ou
+    /// `extend`, déduite des intervalles entre blocs libres, par exemple pour lister les fuites.
+    /// Mêmes exigences que `free_blocks`.
+    pub unsafe fn allocated_blocks(&self) -> AllocIter<'_, MIN> {
+        AllocIter {
+            allocator: self,
+            region: 0,
+            cursor: 0,
+        }
+    }
+
+    /// # Safety
+    /// Retourne le bloc libre, ou la réserve de `set_reserve`, qui commence à la plus petite
+    /// adresse supérieure ou égale à `addr`. Mêmes exigences que `free_blocks`.
+    unsafe fn next_free_block(&self, addr: usize) -> Option<(usize, usize)> {
+        let reserve = Some(self.reserve.get()).filter(|&(start, size)| size > 0 && start >= addr);
+
+        self.free_blocks()
+            .filter(|&(start, _)| start >= addr)
+            .chain(reserve)
+            .min_by_key(|&(start, _)| start)
+    }
+
+    /// # Safety
+    /// Écrit une ligne par bloc libre (adresse, taille, bloc suivant) dans `w`, par exemple
+    /// une liaison série fournie par l'intégrateur. Mêmes exigences que `free_blocks` :
+    /// en particulier, `w` ne doit pas allouer avec cet allocateur.
+    pub unsafe fn dump(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        for head in (*self.free_list.get()).into_iter().chain(self.small_bins.get()) {
+            let mut current = head;
+
+            while !current.is_null() {
+                writeln!(w, "{:#x} taille={} suivant={:p}", (*current).starting_addr(), (*current).size(), (*current).next())?;
+                current = (*current).next();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retourne la taille du plus grand bloc libre (0 si la liste est vide),
+    /// sans tenir compte des contraintes d'alignement.
+    pub fn largest_free_block(&self) -> usize {
+        let _guard = self.free_list.lock();
+        // Le verrou empêche toute modification de la liste pendant le parcours
+        unsafe { self.free_blocks() }.map(|(_, size)| size).max().unwrap_or(0)
+    }
+
+    /// Indique qu'il ne reste plus aucun bloc libre, cases rapides comprises, sans parcourir
+    /// les listes.
+    pub fn is_exhausted(&self) -> bool {
+        let _guard = self.free_list.lock();
+        let heads = unsafe { *self.free_list.get() };
+        heads.into_iter().chain(self.small_bins.get()).all(|head| head.is_null())
+    }
+
+    /// Indique qu'il reste au moins un bloc libre ; complément de `is_exhausted`.
+    pub fn has_free(&self) -> bool {
+        !self.is_exhausted()
+    }
+
+    /// Retourne la tête de la liste libre de chaque classe de taille, pour qu'un outil externe
+    /// (script de débogueur par exemple) parcoure les listes avec `Block::size` et
+    /// `Block::next`. Les blocs des cases rapides s'obtiennent par `small_bin_heads`. Les
+    /// pointeurs ne sont valides que tant que l'allocateur n'est pas modifié.
+    pub fn free_list_heads(&self) -> [*const Block; SIZE_CLASSES] {
+        let _guard = self.free_list.lock();
+        unsafe { *self.free_list.get() }.map(|head| head as *const Block)
+    }
+
+    /// Retourne le sommet de chaque case rapide de `with_small_bins`, à parcourir comme les
+    /// listes de `free_list_heads`.
+    pub fn small_bin_heads(&self) -> [*const Block; SMALL_BINS] {
+        let _guard = self.free_list.lock();
+        self.small_bins.get().map(|head| head as *const Block)
+    }
+
+    /// Retourne la tête de la première liste libre non vide, par classe de taille croissante
+    /// puis case rapide par case rapide, ou un pointeur nul s'il ne reste aucun bloc libre ;
+    /// les autres s'obtiennent par `free_list_heads` et `small_bin_heads`.
+    pub fn free_list_head(&self) -> *const Block {
+        let heads = self.free_list_heads().into_iter().chain(self.small_bin_heads());
+        heads.into_iter().find(|head| !head.is_null()).unwrap_or(ptr::null())
+    }
+
+    /// Retourne le nombre de blocs libres, toutes classes confondues (0 si aucun).
+    /// Il ne diminue qu'à la fusion des blocs adjacents.
+    pub fn count_free_blocks(&self) -> usize {
+        let _guard = self.free_list.lock();
+        unsafe { self.free_blocks() }.count()
+    }
+
+    /// Retourne la somme des tailles de tous les blocs libres, en-têtes compris puisqu'ils
+    /// sont récupérables. Comparée à `largest_free_block`, elle révèle la fragmentation.
+    pub fn total_free(&self) -> usize {
+        let _guard = self.free_list.lock();
+        unsafe { self.free_blocks() }.map(|(_, size)| size).sum()
+    }
+
+    /// Retourne la taille totale des régions confiées à l'allocateur.
+    pub fn heap_size(&self) -> usize {
+        let _guard = self.free_list.lock();
+        self.heap_size.get()
+    }
+
+    /// # Safety
+    /// Retourne la somme des blocs libres, des octets alloués (tailles ajustées), de la
+    /// réserve et de l'arène linéaire : autant d'octets que `heap_size` quand aucun n'est
+    /// perdu. Les fins trop petites pour être découpées ne sont comptées qu'à leur
+    /// libération, avec l'allocation qui les contient. Mêmes exigences que `free_blocks`.
+    pub unsafe fn accounted_bytes(&self) -> usize {
+        let _guard = self.free_list.lock();
+        let free: usize = self.free_blocks().map(|(_, size)| size).sum();
+        let (bump_start, _, bump_end) = self.bump.get();
+
+        free + self.stats.get().allocated_bytes + self.reserve.get().1 + (bump_end - bump_start)
+    }
+
+    /// Indique si les allocations `layouts`, faites dans l'ordre, réussiraient toutes dans
+    /// l'état actuel, sans rien allouer. La simulation place chaque requête dans le premier
+    /// bloc libre qui convient, dans l'ordre de `free_blocks`, en tenant compte des découpages ;
+    /// seuls les `SIMULATED_BLOCKS` premiers blocs libres y participent.
+    pub fn can_allocate_all(&self, layouts: &[Layout]) -> bool {
+        let _guard = self.free_list.lock();
+        let mut blocks = [(0, 0); SIMULATED_BLOCKS];
+        let count = unsafe { self.free_snapshot(&mut blocks) };
+
+        layouts.iter().all(|&layout| {
+            layout.size() == 0
+                || self
+                    .adjust_layout(layout)
+                    .is_some_and(|(size, alignment)| Self::simulate_alloc(&mut blocks[..count], size, alignment))
+        })
+    }
+
+    /// Retourne combien d'allocations supplémentaires de `layout` l'état actuel peut encore
+    /// satisfaire, en les prenant d'affilée dans chaque bloc libre avec leur taille ajustée
+    /// (en-têtes et découpages compris). Retourne `usize::MAX` pour une taille nulle, qui ne
+    /// consomme rien, et 0 pour un layout que l'allocateur ne peut pas servir.
+    pub fn remaining_capacity(&self, layout: Layout) -> usize {
+        if layout.size() == 0 {
+            return usize::MAX;
+        }
+        let Some((size, alignment)) = self.adjust_layout(layout) else {
+            return 0;
+        };
+
+        // La taille ajustée étant un multiple de l'alignement, les allocations suivant la
+        // première d'un bloc se succèdent sans écart
+        let fits = |start: usize, end: usize| {
+            Self::fit_in(start, end, size, alignment).map_or(0, |address| 1 + (end - address - size) / size)
+        };
+
+        let _guard = self.free_list.lock();
+        if self.bump_active() {
+            let (_, cursor, end) = self.bump.get();
+            return fits(cursor, end);
+        }
+
+        unsafe { self.free_blocks() }.map(|(addr, size)| fits(addr, addr + size)).sum()
+    }
+
+    /// # Safety
+    /// Recopie dans `out` les bornes `(début, fin)` des blocs où les allocations seraient prises,
+    /// l'arène seule en allocation linéaire, et retourne leur nombre. L'appelant doit détenir le verrou.
+    unsafe fn free_snapshot(&self, out: &mut [(usize, usize); SIMULATED_BLOCKS]) -> usize {
+        if self.bump_active() {
+            let (_, cursor, end) = self.bump.get();
+            out[0] = (cursor, end);
+            return 1;
+        }
+
+        let mut count = 0;
+        for (slot, (addr, size)) in out.iter_mut().zip(self.free_blocks()) {
+            *slot = (addr, addr + size);
+            count += 1;
+        }
+        count
+    }
+
+    /// Place une allocation de `size` octets ajustés dans le premier bloc de `blocks` qui
+    /// convient et réduit ce bloc comme le ferait un découpage. Retourne `false` si aucun ne convient.
+    fn simulate_alloc(blocks: &mut [(usize, usize)], size: usize, alignment: usize) -> bool {
+        for (start, end) in blocks.iter_mut() {
+            if let Some(address) = Self::fit_in(*start, *end, size, alignment) {
+                // Une fin trop petite pour former un bloc reste dans l'allocation
+                let allocation_end = address + size;
+                *start = if *end - allocation_end >= Self::MIN_BLOCK_SIZE { allocation_end } else { *end };
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Retourne la fragmentation de la mémoire libre, `1 - plus grand bloc / total libre` :
+    /// 0 pour un seul bloc libre, proche de 1 pour une multitude de petits blocs.
+    /// Retourne 0 s'il n'y a aucune mémoire libre.
+    pub fn fragmentation(&self) -> f32 {
+        let _guard = self.free_list.lock();
+        let (largest, total) = unsafe { self.free_blocks() }
+            .fold((0, 0), |(largest, total), (_, size)| (largest.max(size), total + size));
+
+        if total == 0 {
+            return 0.0;
+        }
+        1.0 - largest as f32 / total as f32
+    }
+
+    /// # Safety
+    /// Retourne le début et la taille ajustée du bloc de l'allocation `ptr` faite avec `layout`.
+    /// En mode `header`, la taille est relue avant `ptr` : celle de `layout` est ignorée.
+    #[cfg(feature = "header")]
+    unsafe fn block_of(&self, ptr: *mut u8, layout: Layout) -> Option<(usize, usize)> {
+        Some((ptr as usize - header_offset(layout.align().max(self.min_align)), read_size_header(ptr)))
+    }
+
+    /// # Safety
+    /// Retourne le début et la taille ajustée du bloc de l'allocation `ptr` faite avec `layout`.
+    #[cfg(not(feature = "header"))]
+    unsafe fn block_of(&self, ptr: *mut u8, layout: Layout) -> Option<(usize, usize)> {
+        #[cfg(feature = "debug_tags")]
+        let ptr = ptr.sub(header_offset(layout.align().max(self.min_align)));
+
+        self.adjust_layout(layout).map(|(size, _)| (ptr as usize, size))
+    }
+
+    /// # Safety
+    /// Vérifie les invariants de toutes les listes libres : absence de cycle, tri par adresse
+    /// (sauf avec `with_remainders_at_head`), taille minimale, classe de taille, absence de
+    /// chevauchement et, si des régions ont été enregistrées, appartenance de chaque bloc à
+    /// l'une d'elles. Les listes peuvent être corrompues, mais chaque pointeur `next` non nul
+    /// doit rester lisible.
+    pub unsafe fn validate(&self) -> Result<(), ValidationError> {
+        let _guard = self.free_list.lock();
+        let heads = *self.free_list.get();
+
+        // Le tri ne peut être vérifié que sur des listes finies
+        if heads.iter().any(|&head| has_cycle(head)) {
+            return Err(ValidationError::Cycle);
+        }
+
+        for (class, &head) in heads.iter().enumerate() {
+            let mut previous_block: *mut Block = null_mut();
+            let mut current_block = head;
+
+            while !current_block.is_null() {
+                let block = &*current_block;
+
+                if block.size() < mem::size_of::<Block>() {
+                    return Err(ValidationError::TooSmall);
+                }
+                if size_class(block.size()) != class {
+                    return Err(ValidationError::WrongSizeClass);
+                }
+                if !self.remainders_at_head
+                    && !previous_block.is_null()
+                    && (*previous_block).starting_addr() >= block.starting_addr()
+                {
+                    return Err(ValidationError::Unsorted);
+                }
+                if self.region_count.get() > 0
+                    && (self.region_of(block.starting_addr()).is_none()
+                        || !self.same_region(block.starting_addr(), block.finishing_addr() - 1))
+                {
+                    return Err(ValidationError::OutOfRegion);
+                }
+
+                let overlapping = self.find_free_block(|other| {
+                    !ptr::eq(other, block)
+                        && other.starting_addr() < block.finishing_addr()
+                        && block.starting_addr() < other.finishing_addr()
+                });
+                if overlapping.is_some() {
+                    return Err(ValidationError::Overlap);
+                }
+
+                previous_block = current_block;
+                current_block = block.next();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Place le reste d'un bloc découpé en tête de la liste de sa classe plutôt qu'à sa place
+    /// dans l'ordre des adresses. L'allocation suivante de taille voisine le trouve aussitôt et
+    /// reste proche de la précédente, mais les listes ne sont plus triées : `validate` ne
+    /// vérifie plus le tri et la mémoire tend à se fragmenter davantage.
+    pub const fn with_remainders_at_head(mut self, enabled: bool) -> Self {
+        self.remainders_at_head = enabled;
+        self
+    }
+
+    /// Prend chaque allocation à la fin du bloc choisi plutôt qu'à son début : le tas se
+    /// remplit alors du haut vers le bas et c'est le début du bloc qui retourne dans les
+    /// listes. Réservé, par exemple, à un allocateur dédié aux allocations de longue durée.
+    pub const fn with_from_top(mut self, enabled: bool) -> Self {
+        self.from_top = enabled;
+        self
+    }
+
+    /// Quand aucun bloc ne convient, fusionne les blocs libres adjacents comme `coalesce_all`
+    /// puis recherche une seconde fois avant d'échouer. Seuls des blocs laissés séparés,
+    /// par exemple par `prepare_pool`, peuvent ainsi se regrouper : les pools préparés
+    /// disparaissent au premier échec.
+    pub const fn with_coalesce_retry(mut self, enabled: bool) -> Self {
+        self.coalesce_retry = enabled;
+        self
+    }
+
+    /// Range les blocs libérés d'au plus `SMALL_BIN_MAX` octets dans des cases rapides, une
+    /// par taille, au lieu des listes triées : libération et allocation de même taille s'y
+    /// font en temps constant, sans fusion. Les autres blocs suivent les listes triées et
+    /// fusionnent comme d'habitude. Les cases sont vidées dans ces listes, avec fusion, quand
+    /// une allocation n'y trouve rien ou par `coalesce_all` ; avant cela, leurs blocs ne sont
+    /// vus ni par `free_blocks` ni par `validate`, mais comptent dans `free_bytes`.
+    pub const fn with_small_bins(mut self, enabled: bool) -> Self {
+        self.use_small_bins = enabled;
+        self
+    }
+
+    /// Ajuste la taille et l'alignement pour répondre aux contraintes minimales, dont le
+    /// plancher de `with_min_align`. Retourne `None` si la taille ajustée dépasse les
+    /// limites d'un `Layout`.
+    fn adjust_layout(&self, layout: Layout) -> Option<(usize, usize)> {
+        let layout = layout.align_to(self.min_align).ok()?;
+
+        // Place réservée pour les mots d'en-tête avant les données
+        #[cfg(any(feature = "header", feature = "debug_tags"))]
+        let layout = Layout::from_size_align(layout.size().checked_add(header_offset(layout.align()))?, layout.align()).ok()?;
+
+        // Place réservée pour le canari après les données
+        #[cfg(feature = "canaries")]
+        let layout = Layout::from_size_align(layout.size().checked_add(CANARY.len())?, layout.align()).ok()?;
+
+        // La taille restant un multiple de l'alignement, le reste d'un découpage reste aligné lui aussi
+        let layout = layout.align_to(mem::align_of::<Block>()).ok()?.pad_to_align();
+        let align = layout.align();
+        let size = layout.size().max(Self::MIN_BLOCK_SIZE).checked_add(align - 1)? & !(align - 1);
+
+        // Deux allocations distinctes ne partagent alors aucune ligne de cache
+        if self.line_rounding {
+            return Some((size.checked_next_multiple_of(self.cache_line)?, align.max(self.cache_line)));
+        }
+        Some((size, align))
+    }
+
+    /// Crée un allocateur vide utilisant la stratégie donnée et des blocs d'au moins
+    /// `MIN_BLOCK_SIZE` octets, par exemple `FreeListAllocator::<64>::configured(...)`.
+    pub const fn configured(strategy: Strategy) -> Self {
+        FreeListAllocator {
+            free_list: Spinlock::new([null_mut(); SIZE_CLASSES]),
+            strategy,
+            rover: Cell::new(0),
+            stats: Cell::new(AllocStats::ZERO),
+            heap_size: Cell::new(0),
+            oom_handler: Cell::new(None),
+            alloc_fill: Cell::new(None),
+            #[cfg(feature = "fault_injection")]
+            forced_failures: Cell::new(0),
+            low_watermark: Cell::new(0),
+            watermark_handler: Cell::new(None),
+            watermark_armed: Cell::new(false),
+            release_threshold: Cell::new(0),
+            release_callback: Cell::new(None),
+            regions: UnsafeCell::new([(0, 0); MAX_REGIONS]),
+            region_count: Cell::new(0),
+            reserve: Cell::new((0, 0)),
+            bump: Cell::new((0, 0, 0)),
+            min_align: 1,
+            line_rounding: false,
+            redzone: 0,
+            redzone_at: Cell::new((0, 0)),
+            cache_line: DEFAULT_CACHE_LINE,
+            max_search: Cell::new(0),
+            nodes_visited: Cell::new(0),
+            last_shortfall: Cell::new(0),
+            histogram: Cell::new([0; 32]),
+            #[cfg(feature = "header")]
+            split_threshold: Cell::new(None),
+            placement_rng: Cell::new(0),
+            remainders_at_head: false,
+            from_top: false,
+            coalesce_retry: false,
+            use_small_bins: false,
+            small_bins: Cell::new([null_mut(); SMALL_BINS]),
+            #[cfg(feature = "debug_tags")]
+            current_tag: Cell::new(0),
+            #[cfg(feature = "debug_tags")]
+            tags: Cell::new([(0, 0); MAX_TAGS]),
+        }
+    }
+
+    /// Impose à toutes les allocations un alignement d'au moins `align` octets, quel que soit
+    /// leur `Layout`, par exemple pour un contrôleur DMA. `align` doit être une puissance de deux.
+    pub const fn with_min_align(mut self, align: usize) -> Self {
+        assert!(align.is_power_of_two(), "l'alignement minimal doit être une puissance de deux");
+        self.min_align = align;
+        self
+    }
+
+    /// Garde les `bytes` derniers octets du premier tas confié à `init` (ou `init_clean`,
+    /// `reset`), arrondis à l'alignement des blocs, hors de toute allocation : cette zone
+    /// rouge est remplie d'un motif que `check_redzone` vérifie, pour repérer un débordement
+    /// au-delà de la dernière allocation du tas. Les tas confiés ensuite n'en ont pas.
+    pub const fn with_redzone(mut self, bytes: usize) -> Self {
+        self.redzone = bytes;
+        self
+    }
+
+    /// Vérifie que la zone rouge de `with_redzone` a gardé son motif. Retourne l'adresse du
+    /// premier octet écrasé en cas de débordement ; sans zone rouge, réussit toujours.
+    pub fn check_redzone(&self) -> Result<(), usize> {
+        let _guard = self.free_list.lock();
+        let (start, size) = self.redzone_at.get();
+        if size == 0 {
+            return Ok(());
+        }
+
+        // La zone rouge n'appartient à aucune région : l'allocateur n'y écrit jamais
+        let zone = unsafe { core::slice::from_raw_parts(start as *const u8, size) };
+        match zone.iter().position(|&byte| byte != REDZONE_PATTERN) {
+            Some(offset) => Err(start + offset),
+            None => Ok(()),
+        }
+    }
+
+    /// # Safety
+    /// Retire la zone rouge de `with_redzone` de la fin du tas `heap_start..heap_start + heap_size`,
+    /// la remplit du motif et retourne la taille restante du tas. Une zone déjà en place est
+    /// gardée et le tas rendu entier. L'appelant doit détenir le verrou.
+    unsafe fn carve_redzone(&self, heap_start: usize, heap_size: usize) -> usize {
+        if self.redzone_at.get().1 > 0 {
+            return heap_size;
+        }
+
+        let size = self.redzone.next_multiple_of(mem::align_of::<Block>()).min(heap_size);
+        let start = heap_start + heap_size - size;
+
+        ptr::write_bytes(start as *mut u8, REDZONE_PATTERN, size);
+        self.redzone_at.set((start, size));
+        heap_size - size
+    }
+
+    /// Arrondit chaque allocation à des lignes de cache entières, début et taille, pour que
+    /// deux allocations voisines ne partagent jamais une ligne (faux partage entre cœurs).
+    /// La ligne mesure `DEFAULT_CACHE_LINE` octets sauf avec `with_cache_line_size`.
+    pub const fn with_cache_line_rounding(mut self, enabled: bool) -> Self {
+        self.line_rounding = enabled;
+        self
+    }
+
+    /// Fixe la taille de ligne de cache utilisée par `with_cache_line_rounding`, qui doit être
+    /// une puissance de deux.
+    pub const fn with_cache_line_size(mut self, line: usize) -> Self {
+        assert!(line.is_power_of_two(), "la taille de ligne de cache doit être une puissance de deux");
+        self.cache_line = line;
+        self
+    }
+
+    /// # Safety
+    /// Cette méthode est `unsafe` car elle accède et modifie directement la liste des blocs libres.
+    /// L'appelant doit garantir que la liste est dans un état cohérent avant l'appel.
+    pub unsafe fn find_block(&self, size: usize, alignment: usize) -> Option<(*mut Block, usize)> {
+        let _guard = self.free_list.lock();
+        self.find_block_locked(size, alignment)
+    }
+
+    /// # Safety
+    /// Comme `find_block`, l'appelant détenant déjà le verrou de `free_list`.
+    unsafe fn find_block_locked(&self, size: usize, alignment: usize) -> Option<(*mut Block, usize)> {
+        let heads = self.free_list.get();
+        let first_class = size_class(size);
+        let mut found: Option<(usize, *mut Block, *mut Block, usize)> = None; // (classe, précédent, bloc, adresse)
+        let max_search = self.max_search.get();
+        let mut visited = 0;
+
+        // Les classes inférieures ne contiennent que des blocs trop petits ; worst-fit part des plus grandes
+        'classes: for i in first_class..SIZE_CLASSES {
+            let class = if self.strategy == Strategy::WorstFit { SIZE_CLASSES - 1 - (i - first_class) } else { i };
+            let mut previous_block: *mut Block = null_mut();
+            let mut current_block = (*heads)[class];
+            #[cfg(feature = "debug_checks")]
+            let mut hare = current_block;
+
+            while !current_block.is_null() {
+                // Profondeur de recherche épuisée : on se contente de ce qui a été trouvé
+                if max_search != 0 && visited == max_search {
+                    break 'classes;
+                }
+                visited += 1;
+                self.nodes_visited.set(self.nodes_visited.get() + 1);
+
+                if let Some(allocation_address) = self.placement(current_block, size, alignment) {
+                    let better = match found {
+                        Some((_, _, best_block, best_address)) => {
+                            self.prefers((current_block, allocation_address), (best_block, best_address), size)
+                        }
+                        None => true,
+                    };
+
+                    if better {
+                        found = Some((class, previous_block, current_block, allocation_address));
+                    }
+
+                    if self.stops_at(current_block) {
+                        break;
+                    }
+                }
+
+                previous_block = current_block;
+                current_block = (*current_block).next();
+
+                // Le lièvre avance de deux blocs par tour et ne rattrape le parcours que sur un
+                // cycle : l'allocation échoue alors au lieu de boucler indéfiniment
+                #[cfg(feature = "debug_checks")]
+                {
+                    for _ in 0..2 {
+                        if !hare.is_null() {
+                            hare = (*hare).next();
+                        }
+                    }
+                    if !hare.is_null() && hare == current_block {
+                        return None;
+                    }
+                }
+            }
+
+            // Une classe ne contient que des blocs plus petits que ceux des classes supérieures
+            if found.is_some() {
+                break;
+            }
+        }
+
+        let Some((class, previous_block, mut block, allocation_address)) = found else {
+            self.record_shortfall(size, alignment);
+            return None;
+        };
+        self.unlink(class, previous_block, block);
+        let allocation_address = self.random_placement(block, size, alignment).unwrap_or(allocation_address);
+
+        // Le début sauté pour l'alignement redevient un bloc libre s'il est assez grand ;
+        // le bloc retourné commence alors à l'adresse de l'allocation
+        let gap = allocation_address - (*block).starting_addr();
+        if gap >= Self::MIN_BLOCK_SIZE {
+            let (end, dirty) = ((*block).finishing_addr(), (*block).dirty);
+            let gap_start = (*block).starting_addr();
+
+            let region = (*block).region;
+            block = allocation_address as *mut Block;
+            (*block).set_size(end - allocation_address);
+            (*block).set_next(null_mut());
+            (*block).dirty = dirty;
+            (*block).region = region;
+            self.insert_region(gap_start, gap, dirty);
+        }
+
+        // Le curseur reprend après l'allocation qui vient d'être faite
+        if self.strategy == Strategy::NextFit {
+            self.rover.set(allocation_address + size);
+        }
+
+        Some((block, allocation_address))
+    }
+
+    /// # Safety
+    /// Retient, pour `last_alloc_shortfall`, combien d'octets manquent au bloc libre le plus
+    /// proche de pouvoir accueillir `size` octets alignés sur `alignment`, toutes classes
+    /// confondues. L'appelant doit détenir le verrou.
+    unsafe fn record_shortfall(&self, size: usize, alignment: usize) {
+        let mut shortfall = size;
+
+        // Le tas ne peut pas contenir plus de blocs que ce budget : le parcours s'arrête
+        // même sur une liste corrompue en cycle
+        let mut budget = self.heap_size.get() / mem::size_of::<Block>();
+
+        for &head in (*self.free_list.get()).iter() {
+            let mut current_block = head;
+            while !current_block.is_null() && budget > 0 {
+                budget -= 1;
+                let start = (*current_block).starting_addr().next_multiple_of(alignment);
+                let missing = (start + size).saturating_sub((*current_block).finishing_addr());
+                shortfall = shortfall.min(missing);
+                current_block = (*current_block).next();
+            }
+        }
+
+        self.last_shortfall.set(shortfall);
+    }
+
+    /// # Safety
+    /// Cherche dans toutes les classes un bloc libre vérifiant `predicate` et retourne sa
+    /// classe, son prédécesseur (null s'il est en tête) et le bloc. L'appelant doit détenir le verrou.
+    unsafe fn find_free_block(&self, predicate: impl Fn(&Block) -> bool) -> Option<(usize, *mut Block, *mut Block)> {
+        for (class, &head) in (*self.free_list.get()).iter().enumerate() {
+            let mut previous_block: *mut Block = null_mut();
+            let mut current_block = head;
+
+            while !current_block.is_null() {
+                if predicate(&*current_block) {
+                    return Some((class, previous_block, current_block));
+                }
+
+                previous_block = current_block;
+                current_block = (*current_block).next();
+            }
+        }
+
+        None
+    }
+
+    /// # Safety
+    /// Retire des listes le bloc libre qui commence exactement à `addr`, s'il fait au moins
+    /// `min_size` octets. Retourne un pointeur nul si aucun bloc ne convient.
+    unsafe fn take_block_at(&self, addr: usize, min_size: usize) -> *mut Block {
+        match self.find_free_block(|block| block.starting_addr() == addr) {
+            Some((class, previous_block, block)) if (*block).size() >= min_size => {
+                self.unlink(class, previous_block, block);
+                block
+            }
+            _ => null_mut(),
+        }
+    }
+
+    /// # Safety
+    /// Retire `block` de la mémoire libre ; `previous_block` doit être son prédécesseur
+    /// dans la liste de la classe `class` (null s'il est en tête).
+    unsafe fn unlink(&self, class: usize, previous_block: *mut Block, block: *mut Block) {
+        self.update_stats(|stats| stats.free_bytes -= (*block).size());
+        self.detach(class, previous_block, block);
+    }
+
+    /// # Safety
+    /// Détache `block` de la liste de la classe `class`, sans toucher aux statistiques.
+    unsafe fn detach(&self, class: usize, previous_block: *mut Block, block: *mut Block) {
+        if !previous_block.is_null() {
+            (*previous_block).set_next((*block).next());
+        } else {
+            (*self.free_list.get())[class] = (*block).next();
+        }
+    }
+
+    /// # Safety
+    /// Ajoute `block` à la liste de sa classe de taille, en gardant l'ordre des adresses.
+    unsafe fn link(&self, block: *mut Block) {
+        debug_assert!((*block).size() >= mem::size_of::<Block>(), "bloc plus petit que son en-tête : {} octets", (*block).size());
+
+        let head = &mut (*self.free_list.get())[size_class((*block).size())];
+        let mut previous_block: *mut Block = null_mut();
+        let mut next_block = *head;
+
+        while !next_block.is_null() && (*next_block).starting_addr() < (*block).starting_addr() {
+            previous_block = next_block;
+            next_block = (*next_block).next();
+        }
+
+        (*block).set_next(next_block);
+        if previous_block.is_null() {
+            *head = block;
+        } else {
+            (*previous_block).set_next(block);
+        }
+    }
+
+    /// # Safety
+    /// Écrit un bloc libre de `size` octets à `addr` et le place en tête de la liste de sa
+    /// classe, sans fusion. L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn push_front(&self, addr: usize, size: usize, dirty: bool) {
+        debug_assert!(size >= Self::MIN_BLOCK_SIZE, "reste de découpage trop petit : {} octets", size);
+        self.update_stats(|stats| stats.free_bytes += size);
+
+        let block = addr as *mut Block;
+        let head = &mut (*self.free_list.get())[size_class(size)];
+        (*block).set_size(size);
+        (*block).set_next(*head);
+        (*block).dirty = dirty;
+        (*block).region = self.region_id(addr);
+        *head = block;
+    }
+
+    /// # Safety
+    /// Empile le bloc libéré `addr..addr + size` dans sa case rapide si `with_small_bins` est
+    /// actif et qu'il est assez petit ; retourne `false` sinon. L'appelant doit détenir le verrou.
+    unsafe fn push_small_bin(&self, addr: usize, size: usize) -> bool {
+        let Some(bin) = small_bin(size).filter(|_| self.use_small_bins) else {
+            return false;
+        };
+
+        self.update_stats(|stats| stats.free_bytes += size);
+
+        let mut bins = self.small_bins.get();
+        let block = addr as *mut Block;
+        (*block).set_size(size);
+        (*block).set_next(bins[bin]);
+        (*block).dirty = true;
+        (*block).region = self.region_id(addr);
+        bins[bin] = block;
+        self.small_bins.set(bins);
+        true
+    }
+
+    /// # Safety
+    /// Dépile un bloc de `size` octets aligné sur `alignment` de sa case rapide, avec son
+    /// adresse d'allocation comme `find_block_locked`. Seul le sommet de la pile est examiné.
+    /// L'appelant doit détenir le verrou.
+    unsafe fn pop_small_bin(&self, size: usize, alignment: usize) -> Option<(*mut Block, usize)> {
+        let bin = small_bin(size).filter(|_| self.use_small_bins)?;
+        let mut bins = self.small_bins.get();
+        let block = bins[bin];
+        if block.is_null() || !(*block).starting_addr().is_multiple_of(alignment) {
+            return None;
+        }
+
+        self.update_stats(|stats| stats.free_bytes -= size);
+        bins[bin] = (*block).next();
+        self.small_bins.set(bins);
+        Some((block, (*block).starting_addr()))
+    }
+
+    /// # Safety
+    /// Vide les cases rapides dans les listes triées, où leurs blocs fusionnent avec leurs
+    /// voisins. Retourne `true` si au moins un bloc a été déplacé. L'appelant doit détenir le verrou.
+    unsafe fn flush_small_bins(&self) -> bool {
+        let bins = self.small_bins.replace([null_mut(); SMALL_BINS]);
+        let mut moved = false;
+
+        for mut block in bins {
+            while !block.is_null() {
+                let next_block = (*block).next();
+                let size = (*block).size();
+                self.update_stats(|stats| stats.free_bytes -= size);
+                self.insert_region(block as usize, size, true);
+                block = next_block;
+                moved = true;
+            }
+        }
+        moved
+    }
+
+    /// # Safety
+    /// Remet chaque liste dans l'ordre des adresses, perdu si des restes ont été placés en tête.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn sort_lists(&self) {
+        for class in 0..SIZE_CLASSES {
+            let mut current_block = mem::replace(&mut (*self.free_list.get())[class], null_mut());
+
+            while !current_block.is_null() {
+                let next_block = (*current_block).next();
+                self.link(current_block);
+                current_block = next_block;
+            }
+        }
+    }
+
+    /// # Safety
+    /// Indique si `candidate` doit remplacer `best` selon la stratégie courante, chacun
+    /// accompagné de l'adresse où une allocation de `size` octets y serait placée.
+    /// Les deux pointeurs doivent désigner des blocs valides.
+    unsafe fn prefers(&self, (candidate, candidate_address): (*mut Block, usize), (best, best_address): (*mut Block, usize), size: usize) -> bool {
+        // Octets perdus (début sauté plus reste, soit tout le bloc moins l'allocation), puis
+        // début sauté pour départager deux blocs de même taille
+        let waste = |block: *mut Block, address: usize| {
+            let padding = address - (*block).starting_addr();
+            ((*block).size() - size, padding)
+        };
+
+        match self.strategy {
+            Strategy::FirstFit => false,
+            Strategy::BestFit => (*candidate).size() < (*best).size(),
+            Strategy::WorstFit => (*candidate).size() > (*best).size(),
+            Strategy::NextFit => {
+                let rover = self.rover.get();
+                (*best).starting_addr() < rover && (*candidate).starting_addr() >= rover
+            }
+            Strategy::BestFitAligned => waste(candidate, candidate_address) < waste(best, best_address),
+        }
+    }
+
+    /// # Safety
+    /// Indique si le parcours d'une classe peut s'arrêter sur `candidate`, qui convient.
+    unsafe fn stops_at(&self, candidate: *mut Block) -> bool {
+        match self.strategy {
+            Strategy::FirstFit => true,
+            Strategy::BestFit | Strategy::WorstFit | Strategy::BestFitAligned => false,
+            Strategy::NextFit => (*candidate).starting_addr() >= self.rover.get(),
+        }
+    }
+
+    /// # Safety
+    /// Vérifie si un bloc peut être utilisé pour une allocation et retourne l'adresse alignée
+    /// où la placer. Cette méthode est `unsafe` car elle manipule directement les pointeurs et
+    /// nécessite que `block` pointe vers un bloc valide.
+    pub unsafe fn check_block_allocation(block: *mut Block, size: usize, alignment: usize) -> Option<usize> {
+        // Un bloc dont la fin déborde de l'espace d'adressage est corrompu : rien n'y tient
+        let (start, end) = ((*block).starting_addr(), (*block).checked_finishing_addr()?);
+        Self::fit_in(start, end, size, alignment)
+    }
+
+    /// Corps de `check_block_allocation` pour un bloc libre `start..end`.
+    fn fit_in(start: usize, end: usize, size: usize, alignment: usize) -> Option<usize> {
+        let allocation_address = fit_allocation(start, end, size, alignment).ok()?;
+
+        // Un début sauté trop petit pour redevenir un bloc libre serait perdu : on préfère
+        // l'adresse alignée suivante, qui laisse place à un bloc, si le bloc est assez grand
+        if allocation_address > start && allocation_address - start < Self::MIN_BLOCK_SIZE {
+            if let Ok(address) = fit_allocation(start + Self::MIN_BLOCK_SIZE, end, size, alignment) {
+                return Some(address);
+            }
+        }
+
+        Some(allocation_address)
+    }
+
+    /// # Safety
+    /// Comme `check_block_allocation`, mais place l'allocation à la dernière adresse alignée
+    /// du bloc avec `with_from_top`. `block` doit pointer vers un bloc valide.
+    unsafe fn placement(&self, block: *mut Block, size: usize, alignment: usize) -> Option<usize> {
+        let allocation_address = Self::check_block_allocation(block, size, alignment)?;
+        if !self.from_top {
+            return Some(allocation_address);
+        }
+
+        // Un début trop petit pour redevenir un bloc libre serait perdu : l'allocation
+        // reste alors au début du bloc
+        let top_address = ((*block).finishing_addr() - size) & !(alignment - 1);
+        if top_address - (*block).starting_addr() >= Self::MIN_BLOCK_SIZE {
+            Some(top_address)
+        } else {
+            Some(allocation_address)
+        }
+    }
+
+    /// # Safety
+    /// Tire, avec `set_placement_seed`, une adresse alignée de `block` qui laisse avant et
+    /// après l'allocation de quoi former un bloc libre, ou `None` si le tirage est désactivé
+    /// ou que le bloc est trop petit pour cela. L'appelant doit détenir le verrou.
+    unsafe fn random_placement(&self, block: *mut Block, size: usize, alignment: usize) -> Option<usize> {
+        let state = self.placement_rng.get();
+        if state == 0 {
+            return None;
+        }
+
+        let lowest = ((*block).starting_addr() + Self::MIN_BLOCK_SIZE).checked_next_multiple_of(alignment)?;
+        let highest = (*block).finishing_addr().checked_sub(size + Self::MIN_BLOCK_SIZE)? & !(alignment - 1);
+        if highest < lowest {
+            return None;
+        }
+
+        // Générateur xorshift64 : un état non nul ne redevient jamais nul
+        let mut state = state;
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        self.placement_rng.set(state);
+
+        let positions = ((highest - lowest) / alignment + 1) as u64;
+        Some(lowest + (state % positions) as usize * alignment)
+    }
+
+    /// # Safety
+    /// Insère une région mémoire libre dans la liste de sa classe de taille, triée par adresse,
+    /// et la fusionne avec ses voisins physiquement adjacents. L'appelant doit garantir que :
+    /// - `addr` est aligné correctement.
+    /// - La taille de la région est suffisante pour contenir un bloc.
+    pub unsafe fn insert_free_region(&self, addr: usize, size: usize) {
+        debug_assert!(size >= mem::size_of::<Block>(), "région plus petite qu'un en-tête de bloc : {} octets", size);
+
+        let _guard = self.free_list.lock();
+        self.add_heap_region(addr, size, true);
+    }
+
+    /// # Safety
+    /// Confie une nouvelle région à l'allocateur et l'ajoute à la taille du tas.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn add_heap_region(&self, addr: usize, size: usize, dirty: bool) -> bool {
+        #[cfg(feature = "small_header")]
+        let size = size.min(HEAP_MAX);
+
+        let accepted = self.insert_region(addr, size, dirty);
+        if accepted {
+            self.heap_size.set(self.heap_size.get() + size);
+        }
+        accepted
+    }
+
+    /// # Safety
+    /// Enregistre une région (banque mémoire) et confie sa mémoire à l'allocateur.
+    /// La région est ignorée si `MAX_REGIONS` régions sont déjà enregistrées.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn add_bank(&self, start: usize, size: usize, dirty: bool) {
+        #[cfg(feature = "small_header")]
+        let size = size.min(HEAP_MAX);
+
+        let count = self.region_count.get();
+
+        // La région est enregistrée avant l'insertion pour que son bloc porte son indice
+        if count < MAX_REGIONS {
+            (*self.regions.get())[count] = (start, size);
+            self.region_count.set(count + 1);
+
+            if !self.add_heap_region(start, size, dirty) {
+                self.region_count.set(count);
+            }
+        }
+    }
+
+    /// Retourne le début et la taille du bloc libéré `addr..addr + size`, étendu aux octets
+    /// perdus qui le bordent : fin trop petite pour former un bloc au découpage, début sauté
+    /// pour l'alignement. Dans une région enregistrée, les moins de `MIN_BLOCK_SIZE` octets
+    /// qui séparent le bloc d'un bloc libre ou d'une limite de la région ne peuvent contenir
+    /// aucune allocation et lui reviennent. L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn reclaim_slack(&self, addr: usize, size: usize) -> (usize, usize) {
+        let Some(i) = self.region_of(addr) else {
+            return (addr, size);
+        };
+        let (region_start, region_size) = (*self.regions.get())[i];
+        let (mut start, mut end) = (addr, addr + size);
+        let slack = Self::MIN_BLOCK_SIZE;
+
+        // Fin : jusqu'au bloc libre suivant ou à la fin de la région
+        let next = self.find_free_block(|block| block.starting_addr() > end && block.starting_addr() < end + slack);
+        match next {
+            Some((_, _, block)) if (*block).region as usize == i => end = (*block).starting_addr(),
+            _ if region_start + region_size > end && region_start + region_size < end + slack => {
+                end = region_start + region_size;
+            }
+            _ => {}
+        }
+
+        // Début : depuis la fin du bloc libre précédent ou le début de la région
+        let previous = self.find_free_block(|block| block.finishing_addr() < start && block.finishing_addr() + slack > start);
+        match previous {
+            Some((_, _, block)) if (*block).region as usize == i => start = (*block).finishing_addr(),
+            _ if region_start < start && region_start + slack > start => start = region_start,
+            _ => {}
+        }
+
+        (start, end - start)
+    }
+
+    /// Retourne l'indice de la région contenant `addr`, s'il y en a une.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    fn region_of(&self, addr: usize) -> Option<usize> {
+        let regions = unsafe { &*self.regions.get() };
+        regions[..self.region_count.get()]
+            .iter()
+            .position(|&(start, size)| addr >= start && addr - start < size)
+    }
+
+    /// Retourne l'indice de région à ranger dans l'en-tête d'un bloc commençant à `addr`.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    fn region_id(&self, addr: usize) -> u8 {
+        self.region_of(addr).map_or(NO_REGION, |region| region as u8)
+    }
+
+    /// Indique si deux adresses appartiennent à la même région, condition pour qu'une
+    /// allocation grandisse sur le bloc suivant : deux banques contiguës restent séparées.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    fn same_region(&self, a: usize, b: usize) -> bool {
+        self.region_of(a) == self.region_of(b)
+    }
+
+    /// # Safety
+    /// Comme `insert_free_region`, en précisant si la région a pu être écrite.
+    /// Retourne `false` si la région est rejetée. L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn insert_region(&self, addr: usize, size: usize, dirty: bool) -> bool {
+        let alignment = mem::align_of::<Block>();
+
+        if size < mem::size_of::<Block>() || !addr.is_multiple_of(alignment) {
+            return false;
+        }
+
+        // La région ne doit recouper aucun bloc libre, quelle que soit sa classe
+        #[cfg(feature = "debug_checks")]
+        if let Some((_, _, block)) =
+            self.find_free_block(|block| block.starting_addr() < addr + size && addr < block.finishing_addr())
+        {
+            if (*block).starting_addr() <= addr {
+                panic!("double libération détectée à l'adresse {:#x}", addr);
+            }
+            panic!("chevauchement détecté : la région {:#x}..{:#x} recoupe un bloc libre", addr, addr + size);
+        }
+
+        self.update_stats(|stats| stats.free_bytes += size);
+
+        let mut new_block = addr as *mut Block;
+        (*new_block).set_size(size);
+        (*new_block).set_next(null_mut());
+        (*new_block).dirty = dirty;
+        (*new_block).region = self.region_id(addr);
+
+        // Les blocs de deux régions différentes ne fusionnent jamais, même contigus en adresses :
+        // l'espace entre deux banques peut par exemple correspondre à des entrées-sorties
+
+        // Fusion avec le bloc libre qui commence là où finit la région
+        if let Some((class, previous_block, next_block)) = self.find_free_block(|block| block.starting_addr() == addr + size) {
+            if (*next_block).region == (*new_block).region {
+                self.detach(class, previous_block, next_block);
+                (*new_block).absorb(next_block);
+            }
+        }
+
+        // Fusion avec le bloc libre qui finit là où commence la région
+        if let Some((class, previous_block, block)) = self.find_free_block(|block| block.finishing_addr() == addr) {
+            if (*block).region == (*new_block).region {
+                self.detach(class, previous_block, block);
+                (*block).absorb(new_block);
+                new_block = block;
+            }
+        }
+
+        // Le bloc fusionné peut avoir changé de classe
+        self.link(new_block);
+        true
+    }
+
+    /// # Safety
+    /// Fusionne en une seule passe, par adresse croissante, tous les blocs libres adjacents
+    /// d'une même région. La libération fusionne déjà un bloc avec ses voisins : cette passe
+    /// rattrape les blocs restés séparés, par exemple après une modification directe des listes.
+    /// Aucun bloc libre ne doit être en cours d'utilisation.
+    pub unsafe fn coalesce_all(&self) {
+        let _guard = self.free_list.lock();
+        self.coalesce_locked();
+    }
+
+    /// # Safety
+    /// Déplace les allocations de chaque région vers son début et regroupe sa mémoire libre
+    /// en un bloc à sa fin, la réserve de `set_reserve` restant en place. Après chaque
+    /// déplacement, `relocate(ancien, nouveau, taille)` reçoit la plage de `taille` octets copiée :
+    /// faute de suivi individuel des allocations, une plage regroupe les allocations voisines,
+    /// décalées ensemble de `nouveau - ancien`, à l'appelant d'y retrouver ses pointeurs.
+    /// Les décalages sont des multiples de `COMPACT_ALIGN` : aucune allocation ne doit être
+    /// alignée plus fortement. Aucune allocation ne doit être utilisée pendant l'appel, et
+    /// `relocate`, appelée sous le verrou, ne doit pas se servir de l'allocateur. Sans effet
+    /// en allocation linéaire ; la mémoire de `insert_free_region` hors région n'est pas touchée.
+    pub unsafe fn compact(&self, mut relocate: impl FnMut(*mut u8, *mut u8, usize)) {
+        let _guard = self.free_list.lock();
+        if self.bump_active() {
+            return;
+        }
+        self.flush_small_bins();
+
+        // Un décalage multiple de `step` laisse derrière lui de quoi former un bloc libre
+        let step = COMPACT_ALIGN.max(Self::MIN_BLOCK_SIZE.next_power_of_two());
+
+        for region in 0..self.region_count.get() {
+            let (start, size) = (*self.regions.get())[region];
+            let end = start + size;
+
+            // `cursor` marque la fin de la partie déjà tassée, `pos` la prochaine adresse à examiner
+            let (mut cursor, mut pos) = (start, start);
+            while pos < end {
+                let next_free = self.next_free_block(pos).filter(|&(free_start, _)| free_start < end);
+                match next_free {
+                    Some((free_start, free_size)) if free_start == pos => {
+                        if self.reserve.get() == (free_start, free_size) {
+                            self.insert_gap(cursor, free_start);
+                            cursor = free_start + free_size;
+                        } else if let Some((class, previous_block, block)) =
+                            self.find_free_block(|block| block.starting_addr() == free_start)
+                        {
+                            // Le bloc va être recouvert : il est retiré avant tout déplacement
+                            self.unlink(class, previous_block, block);
+                        }
+                        pos = free_start + free_size;
+                    }
+                    _ => {
+                        let span_end = next_free.map_or(end, |(free_start, _)| free_start);
+                        let len = span_end - pos;
+
+                        let mut target = cursor + (pos - cursor) % step;
+                        if target > cursor && target - cursor < Self::MIN_BLOCK_SIZE {
+                            target += step;
+                        }
+                        let target = target.min(pos);
+
+                        if target != pos {
+                            ptr::copy(pos as *const u8, target as *mut u8, len);
+                            relocate(pos as *mut u8, target as *mut u8, len);
+                        }
+                        self.insert_gap(cursor, target);
+                        cursor = target + len;
+                        pos = span_end;
+                    }
+                }
+            }
+            self.insert_gap(cursor, end);
+        }
+
+        self.rover.set(0);
+    }
+
+    /// # Safety
+    /// Rend aux listes libres la mémoire `start..end` libérée par `compact`, vide ou d'au moins
+    /// `MIN_BLOCK_SIZE` octets. L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn insert_gap(&self, start: usize, end: usize) {
+        if end > start {
+            self.insert_region(start, end - start, true);
+        }
+    }
+
+    /// # Safety
+    /// Corps de `coalesce_all`. L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn coalesce_locked(&self) {
+        self.flush_small_bins();
+
+        let heads = &mut *self.free_list.get();
+        let mut new_heads = [null_mut::<Block>(); SIZE_CLASSES];
+        let mut tails = [null_mut::<Block>(); SIZE_CLASSES];
+        let mut merged: *mut Block = null_mut();
+
+        if self.remainders_at_head {
+            self.sort_lists();
+        }
+
+        // Les listes étant triées, le plus bas des blocs de tête est le suivant par adresse
+        while let Some(class) = (0..SIZE_CLASSES).filter(|&i| !heads[i].is_null()).min_by_key(|&i| heads[i] as usize) {
+            let block = heads[class];
+            heads[class] = (*block).next();
+            (*block).set_next(null_mut());
+
+            if !merged.is_null()
+                && (*merged).finishing_addr() == (*block).starting_addr()
+                && (*merged).region == (*block).region
+            {
+                (*merged).absorb(block);
+                continue;
+            }
+
+            if !merged.is_null() {
+                Self::append(&mut new_heads, &mut tails, merged);
+            }
+            merged = block;
+        }
+
+        if !merged.is_null() {
+            Self::append(&mut new_heads, &mut tails, merged);
+        }
+        *heads = new_heads;
+    }
+
+    /// # Safety
+    /// Libère chaque allocation de `ptrs`, dans l'ordre donné, puis fusionne tous les blocs
+    /// libres adjacents comme `coalesce_all`. Retourne le nombre de blocs libres obtenus :
+    /// 1 par région quand toutes les allocations d'une région ont été rendues. Mêmes
+    /// exigences que `dealloc` pour chaque paire et que `coalesce_all`.
+    pub unsafe fn free_all(&self, ptrs: &[(*mut u8, Layout)]) -> usize {
+        for &(ptr, layout) in ptrs {
+            self.dealloc(ptr, layout);
+        }
+
+        self.coalesce_all();
+        self.count_free_blocks()
+    }
+
+    /// # Safety
+    /// Écrit dans `buf` un instantané des listes libres (cases rapides comprises), des
+    /// statistiques, des régions, du curseur next-fit et du bloc de secours, à rétablir par
+    /// `restore`, et retourne sa taille en octets. Les blocs résidant dans le tas, seuls leur
+    /// adresse, leur taille et leur ordre dans chaque liste sont enregistrés. Si `buf` est trop
+    /// petit, rien n'est écrit et la taille nécessaire est retournée. Mêmes exigences que
+    /// `free_blocks`.
+    pub unsafe fn snapshot(&self, buf: &mut [u8]) -> usize {
+        let _guard = self.free_list.lock();
+
+        let mut count = 0;
+        self.for_each_listed_block(|_, _| count += 1);
+        let regions = &(&*self.regions.get())[..self.region_count.get()];
+        let words = SNAPSHOT_HEADER_WORDS + SNAPSHOT_REGION_WORDS * regions.len() + SNAPSHOT_BLOCK_WORDS * count;
+        let needed = words * mem::size_of::<usize>();
+        if buf.len() < needed {
+            return needed;
+        }
+
+        let mut words = buf.chunks_exact_mut(mem::size_of::<usize>());
+        let mut write = |word: usize| words.next().unwrap().copy_from_slice(&word.to_ne_bytes());
+
+        let stats = self.stats.get();
+        let (reserve_start, reserve_size) = self.reserve.get();
+        for word in [count, stats.allocated_bytes, stats.free_bytes, stats.allocation_count, stats.dealloc_count, stats.peak_allocated] {
+            write(word);
+        }
+        for word in [self.heap_size.get(), self.rover.get(), reserve_start, reserve_size, regions.len()] {
+            write(word);
+        }
+        for &(start, size) in regions {
+            write(start);
+            write(size);
+        }
+        self.for_each_listed_block(|list, block| {
+            write((*block).starting_addr());
+            write((*block).size());
+            write(list);
+            write((*block).dirty as usize | ((*block).region as usize) << 8);
+        });
+        needed
+    }
+
+    /// # Safety
+    /// Rétablit les listes libres, les statistiques, le curseur next-fit et le bloc de secours
+    /// de l'instantané `buf` écrit par `snapshot`, en réécrivant l'en-tête de chaque bloc libre
+    /// enregistré. L'instantané n'est valable que sur un tas inchangé : il est refusé, sans
+    /// rien modifier, si les régions ou la taille du tas diffèrent, si le tampon est tronqué,
+    /// si un bloc est invalide ou si les listes décrites ne passeraient pas `validate`
+    /// (chevauchement, réserve recouverte, tri ou classe de taille). Aucune allocation faite depuis l'instantané ne doit plus être
+    /// utilisée : sa mémoire peut redevenir libre. Un bloc hors des régions enregistrées,
+    /// confié par `insert_free_region`, ne peut pas être vérifié.
+    pub unsafe fn restore(&self, buf: &[u8]) -> Result<(), RestoreError> {
+        let _guard = self.free_list.lock();
+        let word_count = buf.len() / mem::size_of::<usize>();
+        let word = |i: usize| {
+            let bytes = &buf[i * mem::size_of::<usize>()..(i + 1) * mem::size_of::<usize>()];
+            usize::from_ne_bytes(bytes.try_into().unwrap())
+        };
+
+        if word_count < SNAPSHOT_HEADER_WORDS {
+            return Err(RestoreError::Truncated);
+        }
+        let (count, region_count) = (word(0), word(SNAPSHOT_HEADER_WORDS - 1));
+        let blocks_at = region_count
+            .checked_mul(SNAPSHOT_REGION_WORDS)
+            .map(|words| SNAPSHOT_HEADER_WORDS + words);
+        let end = blocks_at.zip(count.checked_mul(SNAPSHOT_BLOCK_WORDS)).and_then(|(at, words)| at.checked_add(words));
+        let (Some(blocks_at), Some(end)) = (blocks_at, end) else {
+            return Err(RestoreError::Truncated);
+        };
+        if word_count < end {
+            return Err(RestoreError::Truncated);
+        }
+
+        // Le tas doit être celui de l'instantané, région par région
+        let regions = &(&*self.regions.get())[..self.region_count.get()];
+        let same_regions = region_count == regions.len()
+            && regions.iter().enumerate().all(|(i, &region)| {
+                let at = SNAPSHOT_HEADER_WORDS + SNAPSHOT_REGION_WORDS * i;
+                region == (word(at), word(at + 1))
+            });
+        if !same_regions || word(6) != self.heap_size.get() {
+            return Err(RestoreError::HeapChanged);
+        }
+
+        // Tous les blocs sont vérifiés avant de toucher au moindre en-tête
+        for i in 0..count {
+            let at = blocks_at + SNAPSHOT_BLOCK_WORDS * i;
+            let (addr, size, list, flags) = (word(at), word(at + 1), word(at + 2), word(at + 3));
+            if list >= SIZE_CLASSES + SMALL_BINS {
+                return Err(RestoreError::InvalidList);
+            }
+            if !self.valid_snapshot_block(addr, size, (flags >> 8) as u8) {
+                return Err(RestoreError::InvalidBlock);
+            }
+        }
+
+        // Puis les invariants des listes, comme `validate` sur les listes rétablies
+        let block = |i: usize| {
+            let at = blocks_at + SNAPSHOT_BLOCK_WORDS * i;
+            (word(at), word(at + 1), word(at + 2))
+        };
+        let reserve = (word(8), word(9));
+        let mut last = [None; SIZE_CLASSES];
+        for i in 0..count {
+            let (addr, size, list) = block(i);
+            let in_class = match list.checked_sub(SIZE_CLASSES) {
+                Some(bin) => small_bin(size) == Some(bin),
+                None => size_class(size) == list,
+            };
+            if !in_class {
+                return Err(RestoreError::Inconsistent(ValidationError::WrongSizeClass));
+            }
+            if list < SIZE_CLASSES {
+                if !self.remainders_at_head && last[list].is_some_and(|previous| previous >= addr) {
+                    return Err(RestoreError::Inconsistent(ValidationError::Unsorted));
+                }
+                last[list] = Some(addr);
+            }
+
+            let overlaps = |(start, len): (usize, usize)| len > 0 && start < addr + size && addr < start.saturating_add(len);
+            if overlaps(reserve) || (0..i).any(|j| overlaps((block(j).0, block(j).1))) {
+                return Err(RestoreError::Inconsistent(ValidationError::Overlap));
+            }
+        }
+
+        self.stats.set(AllocStats {
+            allocated_bytes: word(1),
+            free_bytes: word(2),
+            allocation_count: word(3),
+            dealloc_count: word(4),
+            peak_allocated: word(5),
+        });
+        self.rover.set(word(7));
+        self.reserve.set((word(8), word(9)));
+
+        // Les blocs de chaque liste sont rechaînés dans leur ordre d'origine
+        let mut heads = [null_mut::<Block>(); SIZE_CLASSES + SMALL_BINS];
+        let mut tails = [null_mut::<Block>(); SIZE_CLASSES + SMALL_BINS];
+        for i in 0..count {
+            let at = blocks_at + SNAPSHOT_BLOCK_WORDS * i;
+            let block = word(at) as *mut Block;
+            (*block).set_size(word(at + 1));
+            (*block).set_next(null_mut());
+            let list = word(at + 2);
+            let flags = word(at + 3);
+            (*block).dirty = flags & 1 != 0;
+            (*block).region = (flags >> 8) as u8;
+
+            if tails[list].is_null() {
+                heads[list] = block;
+            } else {
+                (*tails[list]).set_next(block);
+            }
+            tails[list] = block;
+        }
+
+        (*self.free_list.get()).copy_from_slice(&heads[..SIZE_CLASSES]);
+        let mut bins = [null_mut(); SMALL_BINS];
+        bins.copy_from_slice(&heads[SIZE_CLASSES..]);
+        self.small_bins.set(bins);
+        Ok(())
+    }
+
+    /// Indique si un bloc d'instantané peut être rétabli : aligné, assez grand pour son
+    /// en-tête, et contenu dans la région qu'il désigne. L'appelant doit détenir le verrou de
+    /// `free_list`.
+    fn valid_snapshot_block(&self, addr: usize, size: usize, region: u8) -> bool {
+        if !addr.is_multiple_of(mem::align_of::<Block>()) || size < Self::MIN_BLOCK_SIZE || addr.checked_add(size).is_none() {
+            return false;
+        }
+        #[cfg(feature = "small_header")]
+        if size > HEAP_MAX {
+            return false;
+        }
+
+        match self.region_of(addr) {
+            Some(i) => {
+                let (start, region_size) = unsafe { (*self.regions.get())[i] };
+                region as usize == i && addr + size <= start + region_size
+            }
+            None => region == NO_REGION,
+        }
+    }
+
+    /// # Safety
+    /// Appelle `f` avec chaque bloc des listes libres puis des cases rapides, dans l'ordre de
+    /// chaque liste, et son numéro de liste : la classe, ou `SIZE_CLASSES` plus la case.
+    /// L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn for_each_listed_block(&self, mut f: impl FnMut(usize, *mut Block)) {
+        let heads = (*self.free_list.get()).into_iter().chain(self.small_bins.get());
+
+        for (list, mut block) in heads.enumerate() {
+            while !block.is_null() {
+                f(list, block);
+                block = (*block).next();
+            }
+        }
+    }
+
+    /// # Safety
+    /// Découpe la mémoire libre en au plus `count` blocs de `chunk_size` octets, chaînés
+    /// sans fusion : les allocations de cette taille sont ensuite servies par la tête de
+    /// leur classe. Un bloc n'est découpé que s'il garde un reste valide ; les autres restent
+    /// tels quels. Retourne le nombre de blocs créés. Une libération refusionne normalement
+    /// le bloc rendu avec ses voisins libres.
+    pub unsafe fn prepare_pool(&self, chunk_size: usize, count: usize) -> usize {
+        if chunk_size == 0 {
+            return 0;
+        }
+        let Some((chunk, _)) = Layout::from_size_align(chunk_size, 1).ok().and_then(|layout| self.adjust_layout(layout)) else {
+            return 0;
+        };
+
+        let _guard = self.free_list.lock();
+        let mut created = 0;
+
+        // Ni les blocs créés ni les restes ne suffisent pour un nouveau découpage : la boucle se termine
+        while created < count {
+            let Some((class, previous_block, block)) =
+                self.find_free_block(|block| block.size() >= chunk + Self::MIN_BLOCK_SIZE)
+            else {
+                break;
+            };
+            self.detach(class, previous_block, block);
+
+            let (mut start, end, dirty) = ((*block).starting_addr(), (*block).finishing_addr(), (*block).dirty);
+            while created < count && (end - start == chunk || end - start >= chunk + Self::MIN_BLOCK_SIZE) {
+                self.link_new_block(start, chunk, dirty);
+                start += chunk;
+                created += 1;
+            }
+
+            if start < end {
+                self.link_new_block(start, end - start, dirty);
+            }
+        }
+
+        created
+    }
+
+    /// # Safety
+    /// Écrit un en-tête de bloc libre à `addr` et l'ajoute à sa liste, sans fusion ni
+    /// mise à jour des statistiques. L'appelant doit détenir le verrou de `free_list`.
+    unsafe fn link_new_block(&self, addr: usize, size: usize, dirty: bool) {
+        let block = addr as *mut Block;
+        (*block).set_size(size);
+        (*block).set_next(null_mut());
+        (*block).dirty = dirty;
+        (*block).region = self.region_id(addr);
+        self.link(block);
+    }
+
+    /// # Safety
+    /// Ajoute `block` en fin de la liste de sa classe dans `heads`, dont `tails` retient les
+    /// derniers blocs ; les blocs doivent arriver par adresse croissante.
+    unsafe fn append(heads: &mut [*mut Block; SIZE_CLASSES], tails: &mut [*mut Block; SIZE_CLASSES], block: *mut Block) {
+        let class = size_class((*block).size());
+        if tails[class].is_null() {
+            heads[class] = block;
+        } else {
+            (*tails[class]).set_next(block);
+        }
+        tails[class] = block;
+    }
+
+    /// # Safety
+    /// Initialise l'allocateur en insérant une région mémoire libre couvrant
+    /// la totalité de l'espace mémoire disponible. Un tas mal aligné est réduit à sa plus
+    /// grande sous-région alignée pour un en-tête de bloc, plutôt que d'être ignoré.
+    pub unsafe fn init(&self, heap_start: usize, heap_size: usize) {
+        let (heap_start, heap_size) = align_region(heap_start, heap_size);
+        let _guard = self.free_list.lock();
+        let heap_size = self.carve_redzone(heap_start, heap_size);
+        self.add_bank(heap_start, heap_size, true);
+    }
+
+    /// # Safety
+    /// Comme `init`, mais indique pourquoi la région est refusée au lieu de l'ignorer.
+    pub unsafe fn try_init(&self, heap_start: usize, heap_size: usize) -> Result<(), InitError> {
+        if !heap_start.is_multiple_of(mem::align_of::<Block>()) {
+            return Err(InitError::Misaligned);
+        }
+        if heap_size < mem::size_of::<Block>() {
+            return Err(InitError::TooSmall);
+        }
+
+        self.init(heap_start, heap_size);
+        Ok(())
+    }
+
+    /// # Safety
+    /// Abandonne toutes les allocations et régions en cours puis réinitialise l'allocateur
+    /// comme `init` sur un allocateur neuf ; seul le gestionnaire d'échec est conservé.
+    /// L'appelant garantit qu'aucune allocation antérieure ne sera plus utilisée.
+    pub unsafe fn reset(&self, heap_start: usize, heap_size: usize) {
+        let _guard = self.free_list.lock();
+
+        *self.free_list.get() = [null_mut(); SIZE_CLASSES];
+        self.small_bins.set([null_mut(); SMALL_BINS]);
+        self.rover.set(0);
+        self.stats.set(AllocStats::ZERO);
+        self.heap_size.set(0);
+        self.region_count.set(0);
+        self.reserve.set((0, 0));
+        self.bump.set((0, 0, 0));
+        self.redzone_at.set((0, 0));
+        self.nodes_visited.set(0);
+        self.last_shortfall.set(0);
+        self.histogram.set([0; 32]);
+        #[cfg(feature = "debug_tags")]
+        self.tags.set([(0, 0); MAX_TAGS]);
+
+        let (heap_start, heap_size) = align_region(heap_start, heap_size);
+        let heap_size = self.carve_redzone(heap_start, heap_size);
+        self.add_bank(heap_start, heap_size, true);
+    }
+
+    /// # Safety
+    /// Ajoute une région mémoire disjointe (autre banque de RAM) après `init`.
+    /// L'appelant doit garantir que la région est valide, inutilisée et ne chevauche
+    /// aucune région déjà confiée à l'allocateur.
+    pub unsafe fn add_region(&self, start: usize, size: usize) {
+        let _guard = self.free_list.lock();
+        self.add_bank(start, size, true);
+    }
+
+    /// # Safety
+    /// Agrandit le tas avec de la mémoire supplémentaire. Si `additional_start` suit
+    /// directement la région la plus haute, celle-ci est prolongée et la nouvelle mémoire
+    /// fusionne avec son dernier bloc libre ; sinon elle devient une nouvelle région.
+    /// Mêmes exigences que `add_region`.
+    pub unsafe fn extend(&self, additional_start: usize, additional_size: usize) {
+        let _guard = self.free_list.lock();
+        let regions = self.regions.get();
+        let count = self.region_count.get();
+
+        let top = (0..count).max_by_key(|&i| (*regions)[i].0);
+        match top {
+            Some(i) if (*regions)[i].0 + (*regions)[i].1 == additional_start => {
+                // La région est prolongée avant l'insertion pour autoriser la fusion
+                (*regions)[i].1 += additional_size;
+                if !self.add_heap_region(additional_start, additional_size, true) {
+                    (*regions)[i].1 -= additional_size;
+                }
+            }
+            _ => self.add_bank(additional_start, additional_size, true),
+        }
+    }
+
+    /// # Safety
+    /// Comme `init`, mais l'appelant garantit que la région est entièrement à zéro :
+    /// `alloc_zeroed` n'aura pas à effacer les blocs jamais écrits.
+    pub unsafe fn init_clean(&self, heap_start: usize, heap_size: usize) {
+        let (heap_start, heap_size) = align_region(heap_start, heap_size);
+        let _guard = self.free_list.lock();
+        let heap_size = self.carve_redzone(heap_start, heap_size);
+        self.add_bank(heap_start, heap_size, false);
+    }
+
+    /// # Safety
+    /// Comme `init`, mais remet d'abord toute la région à zéro : le contenu des
+    /// allocations ne dépend plus de ce que le tas contenait auparavant.
+    pub unsafe fn init_zeroed(&self, heap_start: usize, heap_size: usize) {
+        ptr::write_bytes(heap_start as *mut u8, 0, heap_size);
+        self.init_clean(heap_start, heap_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C, align(16))]
+    struct Heap([u8; 1024]);
+
+    /// Recopie les adresses des blocs libres dans `out` et retourne leur nombre.
+    unsafe fn free_addresses<const N: usize>(allocator: &FreeListAllocator, out: &mut [usize; N]) -> usize {
+        let mut count = 0;
+
+        for (slot, (addr, _)) in out.iter_mut().zip(allocator.free_blocks()) {
+            *slot = addr;
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Recopie les tailles des blocs libres dans `out` et retourne leur nombre.
+    unsafe fn free_sizes<const N: usize>(allocator: &FreeListAllocator, out: &mut [usize; N]) -> usize {
+        let mut count = 0;
+
+        for (slot, (_, size)) in out.iter_mut().zip(allocator.free_blocks()) {
+            *slot = size;
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Retourne la classe dont la liste contient le bloc libre commençant à `addr`.
+    unsafe fn class_of(allocator: &FreeListAllocator, addr: usize) -> Option<usize> {
+        allocator
+            .find_free_block(|block| block.starting_addr() == addr)
+            .map(|(class, _, _)| class)
+    }
+
+    /// Retourne le début du bloc dont `layout` a obtenu le pointeur `ptr`, qui le suit de
+    /// l'en-tête en modes `header` et `debug_tags`.
+    fn block_start(ptr: *mut u8, layout: Layout) -> usize {
+        #[cfg(any(feature = "header", feature = "debug_tags"))]
+        let ptr = ptr.wrapping_sub(header_offset(layout.align()));
+        #[cfg(not(any(feature = "header", feature = "debug_tags")))]
+        let _ = layout;
+
+        ptr as usize
+    }
+
+    #[test]
+    fn free_list_reste_triee() {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+
+        unsafe {
+            // Régions de 64 octets séparées par des trous : aucune fusion possible
+            for offset in [512, 128, 896, 0, 256] {
+                allocator.insert_free_region(base + offset, 64);
+            }
+
+            let mut addrs = [0; 8];
+            let count = free_addresses(&allocator, &mut addrs);
+
+            assert_eq!(count, 5);
+            assert!(
+                addrs[..count].windows(2).all(|w| w[0] < w[1]),
+                "La liste des blocs libres n'est pas triée par adresse."
+            );
+        }
+    }
+
+    #[test]
+    fn blocs_ranges_par_classe_de_taille() {
+        #[repr(C, align(16))]
+        struct LargeHeap([u8; 4096]);
+
+        let mut heap = Heap([0; 1024]);
+        let mut large_heap = LargeHeap([0; 4096]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+
+        unsafe {
+            allocator.insert_free_region(base, 32);
+            allocator.insert_free_region(base + 128, 64);
+            allocator.insert_free_region(base + 256, 200);
+            allocator.insert_free_region(base + 512, 512);
+            allocator.insert_free_region(large_heap.0.as_mut_ptr() as usize, 4096);
+
+            assert_eq!(class_of(&allocator, base), Some(1));
+            assert_eq!(class_of(&allocator, base + 128), Some(2));
+            assert_eq!(class_of(&allocator, base + 256), Some(3));
+            assert_eq!(class_of(&allocator, base + 512), Some(5));
+            assert_eq!(
+                class_of(&allocator, large_heap.0.as_mut_ptr() as usize),
+                Some(SIZE_CLASSES - 1),
+                "Les grands blocs doivent aboutir dans la dernière classe."
+            );
+
+            // Une petite requête est servie par la plus petite classe, même si un bloc la précède
+            let layout = Layout::from_size_align(8, 8).unwrap();
+            assert_eq!(block_start(allocator.alloc(layout), layout), base, "La requête aurait dû puiser dans la classe 1.");
+        }
+    }
+
+    #[test]
+    fn liberation_retourne_dans_la_bonne_classe() {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let other = Layout::from_size_align(200, 8).unwrap();
+        let (small_size, _) = FreeListAllocator::new().adjust_layout(small).unwrap();
+
+        unsafe {
+            allocator.init(base, 1024);
+
+            let a = allocator.alloc(small);
+            let b = allocator.alloc(other);
+            assert!(!a.is_null() && !b.is_null(), "L'allocation a échoué.");
+
+            // `b` empêche la fusion : le bloc libéré garde sa taille et rejoint sa classe
+            allocator.dealloc(a, small);
+            assert_eq!(class_of(&allocator, block_start(a, small)), Some(size_class(small_size)));
+
+            // En libérant `b`, tout fusionne en un seul bloc, rangé dans la classe de 1024 octets
+            allocator.dealloc(b, other);
+            assert_eq!(class_of(&allocator, base), Some(size_class(1024)));
+            assert_eq!(allocator.free_blocks().count(), 1);
+        }
+    }
+
+    #[test]
+    fn alignement_sans_debordement() {
+        // Bloc fictif tout en haut de l'espace d'adressage : l'arrondi déborderait
+        assert_eq!(fit_allocation(usize::MAX - 64, usize::MAX, 16, 4096), Err(()));
+        assert_eq!(fit_allocation(usize::MAX - 64, usize::MAX, usize::MAX, 8), Err(()));
+        assert_eq!(fit_allocation(usize::MAX - 63, usize::MAX, 16, 64), Ok(usize::MAX - 63));
+        assert_eq!(fit_allocation(0x1008, 0x2000, 64, 0x100), Ok(0x1100));
+    }
+
+    #[test]
+    #[should_panic(expected = "alignement invalide")]
+    fn alignement_non_puissance_de_deux() {
+        let _ = fit_allocation(0x1000, 0x2000, 16, 24);
+    }
+
+    #[test]
+    fn validation_d_un_tas_sain() {
+        let mut heap = Heap([0; 1024]);
+        let allocator = FreeListAllocator::new();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+
+        unsafe {
+            allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+            let blocks = [(); 4].map(|_| allocator.alloc(layout));
+            allocator.dealloc(blocks[0], layout);
+            allocator.dealloc(blocks[2], layout);
+
+            assert_eq!(allocator.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn validation_detecte_un_cycle() {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+
+        unsafe {
+            allocator.insert_free_region(base, 64);
+            allocator.insert_free_region(base + 128, 64);
+
+            // Le dernier bloc de la liste pointe de nouveau vers le premier
+            (*((base + 128) as *mut Block)).set_next(base as *mut Block);
+            assert_eq!(allocator.validate(), Err(ValidationError::Cycle));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "small_header"))]
+    fn bloc_de_taille_demesuree_rejete() {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+
+        unsafe {
+            allocator.insert_free_region(base, 64);
+
+            // Une taille corrompue qui ferait déborder la fin du bloc ne doit pas sembler suffire
+            let block = base as *mut Block;
+            (*block).set_size(usize::MAX - 8);
+            assert_eq!((*block).finishing_addr(), usize::MAX);
+            assert_eq!(FreeListAllocator::<DEFAULT_MIN_BLOCK_SIZE>::check_block_allocation(block, 128, 8), None);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "debug_checks")]
+    fn allocation_sur_une_liste_cyclique_echoue() {
+        use core::sync::atomic::{AtomicBool, Ordering};
+
+        static OOM_CALLED: AtomicBool = AtomicBool::new(false);
+
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+        allocator.set_oom_handler(|_| OOM_CALLED.store(true, Ordering::SeqCst));
+
+        unsafe {
+            allocator.insert_free_region(base, 112);
+
+            // Le bloc pointe vers lui-même ; trop petit pour la requête de même classe, il est revisité sans fin
+            (*(base as *mut Block)).set_next(base as *mut Block);
+            let ptr = allocator.alloc(Layout::from_size_align(120, 8).unwrap());
+
+            assert!(ptr.is_null(), "L'allocation sur une liste cyclique aurait dû échouer.");
+            assert!(OOM_CALLED.load(Ordering::SeqCst), "Le gestionnaire d'échec n'a pas été appelé.");
+        }
+    }
+
+    #[test]
+    fn fusion_globale_des_blocs_adjacents() {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+
+        unsafe {
+            // Quatre blocs adjacents chaînés dans le désordre, sans la fusion de la libération
+            for i in [2, 0, 3, 1] {
+                let block = (base + i * 128) as *mut Block;
+                (*block).set_size(128);
+                (*block).dirty = true;
+                (*block).region = NO_REGION;
+                allocator.link(block);
+            }
+            allocator.insert_free_region(base + 768, 64);
+            assert_eq!(allocator.count_free_blocks(), 5);
+
+            allocator.coalesce_all();
+
+            // Le bloc isolé reste séparé ; les listes sont parcourues par classe croissante
+            let mut sizes = [0; 4];
+            assert_eq!(free_sizes(&allocator, &mut sizes), 2, "Les blocs adjacents n'ont pas fusionné.");
+            assert_eq!(sizes[..2], [64, 512]);
+            assert_eq!(allocator.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn reste_de_decoupage_place_en_tete() {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new().with_remainders_at_head(true);
+        let layout = Layout::from_size_align(304, 8).unwrap();
+        let (size, _) = allocator.adjust_layout(layout).unwrap();
+
+        unsafe {
+            // Un bloc trop petit de la classe 256..511, puis un grand bloc à découper
+            allocator.insert_free_region(base, 280);
+            allocator.insert_free_region(base + 288, 640);
+
+            let first = allocator.alloc(layout);
+            assert_eq!(block_start(first, layout), base + 288);
+
+            // Le reste du découpage passe devant le petit bloc de plus basse adresse
+            let remainder = 640 - size;
+            let heads = *allocator.free_list.get();
+            assert_eq!(heads[size_class(remainder)] as usize, base + 288 + size, "Le reste n'a pas été placé en tête.");
+            assert_eq!((*heads[size_class(remainder)]).next() as usize, base);
+            assert_eq!(allocator.validate(), Ok(()));
+
+            // L'allocation suivante de même taille suit immédiatement la précédente
+            let second = allocator.alloc(layout);
+            assert_eq!(second as usize, first as usize + size);
+        }
+    }
+
+    #[test]
+    fn worst_fit_evite_les_petits_restes() {
+        let mut first_heap = Heap([0; 1024]);
+        let mut worst_heap = Heap([0; 1024]);
+        let first_fit = FreeListAllocator::with_strategy(Strategy::FirstFit);
+        let worst_fit = FreeListAllocator::with_strategy(Strategy::WorstFit);
+        let layout = Layout::from_size_align(48, 8).unwrap();
+        let (size, _) = FreeListAllocator::new().adjust_layout(layout).unwrap();
+
+        unsafe {
+            for (allocator, heap) in [(&first_fit, &mut first_heap), (&worst_fit, &mut worst_heap)] {
+                let base = heap.0.as_mut_ptr() as usize;
+                allocator.insert_free_region(base, 256);
+                allocator.insert_free_region(base + 384, 512);
+
+                // Trois allocations laissent un reste au premier bloc, même avec tous les en-têtes
+                for _ in 0..3 {
+                    assert!(!allocator.alloc(layout).is_null());
+                }
+            }
+
+            let mut sizes = [0; 4];
+
+            // First-fit épuise le premier bloc et n'en laisse qu'un petit reste
+            let count = free_sizes(&first_fit, &mut sizes);
+            assert_eq!(&sizes[..count], &[256 - 3 * size, 512]);
+
+            // Worst-fit puise toujours dans le plus grand bloc et garde de grands restes
+            let count = free_sizes(&worst_fit, &mut sizes);
+            assert_eq!(&sizes[..count], &[256, 512 - 3 * size]);
+        }
+    }
+}
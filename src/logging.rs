@@ -0,0 +1,50 @@
+use core::alloc::{GlobalAlloc, Layout};
+
+// Enrobage qui signale chaque opération d'un allocateur.
+
+// Après chaque allocation ou libération, la fonction fournie reçoit le `Layout` et le
+// pointeur concernés, par exemple pour tracer les échanges avec l'allocateur dans un
+// banc de test. Les opérations sont ensuite déléguées telles quelles.
+
+/// Opération signalée par `Logging`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// Allocation de `layout`, ayant retourné `ptr` (nul en cas d'échec).
+    Alloc { layout: Layout, ptr: *mut u8 },
+    /// Libération de `ptr`, alloué avec `layout`.
+    Dealloc { layout: Layout, ptr: *mut u8 },
+}
+
+pub struct Logging<A> {
+    inner: A,       // Allocateur qui sert réellement les requêtes
+    log: fn(Event), // Appelée pour chaque opération
+}
+
+impl<A> Logging<A> {
+    /// Enrobe `inner` en signalant chaque opération à `log`.
+    pub const fn new(inner: A, log: fn(Event)) -> Self {
+        Logging { inner, log }
+    }
+
+    /// Retourne l'allocateur enrobé, par exemple pour l'initialiser.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Logging<A> {
+    /// # Safety
+    /// Mêmes exigences que `GlobalAlloc::alloc`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        (self.log)(Event::Alloc { layout, ptr });
+        ptr
+    }
+
+    /// # Safety
+    /// `ptr` doit provenir de cet allocateur avec le même `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        (self.log)(Event::Dealloc { layout, ptr });
+        self.inner.dealloc(ptr, layout);
+    }
+}
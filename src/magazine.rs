@@ -0,0 +1,167 @@
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::RefCell;
+use core::ptr::null_mut;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// Cache par thread des blocs récemment libérés (« magasin »).
+
+// Chaque thread garde jusqu'à `MAGAZINE_SIZE` blocs libérés d'un même `Layout`. Une
+// allocation de ce layout reprend le dernier d'entre eux sans toucher à l'allocateur
+// enrobé, donc sans prendre son verrou ; une libération qui trouve le magasin plein le
+// vide d'abord dans l'allocateur enrobé. Un thread n'a qu'un magasin, attaché au premier
+// `Magazine` qui le remplit : les autres y passent directement à l'allocateur enrobé. Le
+// magasin d'un thread qui se termine est vidé dans son propriétaire, qui doit donc survivre
+// aux threads qui s'en servent, comme un allocateur global statique.
+
+/// Nombre de blocs gardés par le magasin de chaque thread.
+pub const MAGAZINE_SIZE: usize = 16;
+
+struct Rounds {
+    owner: usize,                     // Adresse du `Magazine` propriétaire, 0 tant qu'aucun
+    flush: Option<unsafe fn(usize, &mut Rounds)>, // Vide le magasin dans son propriétaire
+    layout: Layout,                   // Layout commun à tous les blocs du magasin
+    blocks: [*mut u8; MAGAZINE_SIZE], // Blocs libérés, le plus récent en dernier
+    count: usize,                     // Nombre de blocs présents
+}
+
+impl Drop for Rounds {
+    fn drop(&mut self) {
+        // Le thread se termine : ses blocs retournent à l'allocateur central
+        if let Some(flush) = self.flush {
+            unsafe { flush(self.owner, self) };
+        }
+    }
+}
+
+std::thread_local! {
+    static ROUNDS: RefCell<Rounds> = const {
+        RefCell::new(Rounds { owner: 0, flush: None, layout: Layout::new::<u8>(), blocks: [null_mut(); MAGAZINE_SIZE], count: 0 })
+    };
+}
+
+/// # Safety
+/// Vide `rounds` dans le `Magazine<A>` d'adresse `owner`, qui doit être encore en vie.
+unsafe fn flush_into<A: GlobalAlloc>(owner: usize, rounds: &mut Rounds) {
+    (*(owner as *const Magazine<A>)).flush_rounds(rounds);
+}
+
+pub struct Magazine<A> {
+    inner: A,             // Allocateur central, partagé entre les threads
+    central: AtomicUsize, // Nombre d'opérations transmises à l'allocateur central
+}
+
+impl<A> Magazine<A> {
+    /// Place un magasin par thread devant `inner`.
+    pub const fn new(inner: A) -> Self {
+        Magazine { inner, central: AtomicUsize::new(0) }
+    }
+
+    /// Retourne l'allocateur central, par exemple pour l'initialiser.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Retourne le nombre d'allocations et de libérations transmises à l'allocateur central,
+    /// tous threads confondus.
+    pub fn central_operations(&self) -> usize {
+        self.central.load(Ordering::Relaxed)
+    }
+
+    fn id(&self) -> usize {
+        self as *const Self as usize
+    }
+}
+
+impl<A> Drop for Magazine<A> {
+    fn drop(&mut self) {
+        // Le magasin du thread courant ne doit pas survivre à son propriétaire. Ceux des
+        // autres threads doivent avoir été vidés par `flush`.
+        let _ = ROUNDS.try_with(|rounds| {
+            let Ok(mut rounds) = rounds.try_borrow_mut() else {
+                return;
+            };
+            if rounds.owner == self.id() {
+                if let Some(flush) = rounds.flush.take() {
+                    unsafe { flush(rounds.owner, &mut rounds) };
+                }
+                rounds.owner = 0;
+            }
+        });
+    }
+}
+
+impl<A: GlobalAlloc> Magazine<A> {
+    /// Rend à l'allocateur central les blocs gardés par le magasin du thread courant, ce que
+    /// la fin du thread fait aussi.
+    pub fn flush(&self) {
+        let _ = ROUNDS.try_with(|rounds| {
+            let mut guard = rounds.borrow_mut();
+            let rounds = &mut *guard;
+            if rounds.owner == self.id() {
+                unsafe { self.flush_rounds(rounds) };
+            }
+        });
+    }
+
+    /// # Safety
+    /// Vide `rounds`, qui appartient à ce magasin, dans l'allocateur central.
+    unsafe fn flush_rounds(&self, rounds: &mut Rounds) {
+        for &ptr in &rounds.blocks[..rounds.count] {
+            self.central.fetch_add(1, Ordering::Relaxed);
+            self.inner.dealloc(ptr, rounds.layout);
+        }
+        rounds.count = 0;
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Magazine<A> {
+    /// # Safety
+    /// Mêmes exigences que `GlobalAlloc::alloc`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // Pendant la destruction des variables du thread, le magasin n'est plus accessible
+        let cached = ROUNDS.try_with(|rounds| {
+            let mut guard = rounds.borrow_mut();
+            let rounds = &mut *guard;
+            if rounds.owner != self.id() || rounds.layout != layout || rounds.count == 0 {
+                return None;
+            }
+            rounds.count -= 1;
+            Some(rounds.blocks[rounds.count])
+        });
+
+        cached.ok().flatten().unwrap_or_else(|| {
+            self.central.fetch_add(1, Ordering::Relaxed);
+            self.inner.alloc(layout)
+        })
+    }
+
+    /// # Safety
+    /// `ptr` doit provenir de cet allocateur avec le même `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let cached = ROUNDS.try_with(|rounds| {
+            let mut guard = rounds.borrow_mut();
+            let rounds = &mut *guard;
+            // Un magasin vide peut changer de propriétaire et de layout
+            if rounds.count == 0 {
+                rounds.owner = self.id();
+                rounds.flush = Some(flush_into::<A>);
+                rounds.layout = layout;
+            }
+            if rounds.owner != self.id() || rounds.layout != layout || layout.size() == 0 {
+                return false;
+            }
+
+            if rounds.count == MAGAZINE_SIZE {
+                self.flush_rounds(rounds);
+            }
+            rounds.blocks[rounds.count] = ptr;
+            rounds.count += 1;
+            true
+        });
+
+        if cached != Ok(true) {
+            self.central.fetch_add(1, Ordering::Relaxed);
+            self.inner.dealloc(ptr, layout);
+        }
+    }
+}
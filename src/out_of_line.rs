@@ -0,0 +1,191 @@
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::ptr::{self, null_mut};
+
+use crate::spinlock::Spinlock;
+
+// Allocateur à liste libre hors du tas.
+
+// Les plages libres ne sont pas chaînées dans la mémoire qu'elles décrivent, comme les
+// `Block` de `FreeListAllocator`, mais rangées par adresse croissante dans un tableau de
+// descripteurs fourni à `init`. Une écriture débordant d'une allocation ne peut donc pas
+// corrompre la liste libre ; en échange, le nombre de plages est borné par ce tableau.
+
+/// Descripteur d'une plage libre du tas.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FreeRange {
+    /// Adresse de début de la plage.
+    pub start: usize,
+    /// Taille de la plage en octets.
+    pub size: usize,
+}
+
+impl FreeRange {
+    /// Retourne l'adresse qui suit immédiatement la plage.
+    fn end(&self) -> usize {
+        self.start + self.size
+    }
+}
+
+pub struct OutOfLineAllocator {
+    ranges: Spinlock<*mut FreeRange>, // Tableau des descripteurs ; le verrou protège tout l'état
+    capacity: Cell<usize>,            // Nombre de descripteurs du tableau
+    len: Cell<usize>,                 // Nombre de plages libres, rangées par adresse croissante
+    lost: Cell<usize>,                // Octets libérés sans descripteur disponible pour les décrire
+}
+
+/// # Safety
+/// Toutes les modifications du tableau et des champs se font sous le verrou de `ranges`.
+unsafe impl Sync for OutOfLineAllocator {}
+
+impl Default for OutOfLineAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for OutOfLineAllocator {
+    /// # Safety
+    /// Mêmes exigences que `GlobalAlloc::alloc`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // Une allocation de taille nulle n'occupe aucune plage
+        if layout.size() == 0 {
+            return layout.align() as *mut u8;
+        }
+
+        let _guard = self.ranges.lock();
+        let ranges = *self.ranges.get();
+
+        for i in 0..self.len.get() {
+            let range = *ranges.add(i);
+            let Some(address) = range.start.checked_next_multiple_of(layout.align()) else {
+                continue;
+            };
+            let allocation_end = match address.checked_add(layout.size()) {
+                Some(allocation_end) if allocation_end <= range.end() => allocation_end,
+                _ => continue,
+            };
+
+            let head = FreeRange { start: range.start, size: address - range.start };
+            let tail = FreeRange { start: allocation_end, size: range.end() - allocation_end };
+            match (head.size > 0, tail.size > 0) {
+                (false, false) => self.remove(i),
+                (false, true) => *ranges.add(i) = tail,
+                (true, false) => *ranges.add(i) = head,
+                (true, true) => {
+                    // Couper la plage en deux demande un descripteur de plus
+                    if self.len.get() == self.capacity.get() {
+                        continue;
+                    }
+                    *ranges.add(i) = head;
+                    self.insert(i + 1, tail);
+                }
+            }
+            return address as *mut u8;
+        }
+
+        null_mut()
+    }
+
+    /// # Safety
+    /// `ptr` doit provenir de cet allocateur avec le même `layout`.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if layout.size() == 0 {
+            return;
+        }
+
+        let _guard = self.ranges.lock();
+        let ranges = *self.ranges.get();
+        let freed = FreeRange { start: ptr as usize, size: layout.size() };
+
+        // Première plage située après la zone rendue
+        let mut i = 0;
+        while i < self.len.get() && (*ranges.add(i)).start < freed.start {
+            i += 1;
+        }
+
+        let merges_previous = i > 0 && (*ranges.add(i - 1)).end() == freed.start;
+        let merges_next = i < self.len.get() && (*ranges.add(i)).start == freed.end();
+        match (merges_previous, merges_next) {
+            (true, true) => {
+                (*ranges.add(i - 1)).size += freed.size + (*ranges.add(i)).size;
+                self.remove(i);
+            }
+            (true, false) => (*ranges.add(i - 1)).size += freed.size,
+            (false, true) => {
+                let next = &mut *ranges.add(i);
+                *next = FreeRange { start: freed.start, size: freed.size + next.size };
+            }
+            (false, false) if self.len.get() < self.capacity.get() => self.insert(i, freed),
+            // Sans descripteur libre, la zone est perdue plutôt que décrite dans le tas
+            (false, false) => self.lost.set(self.lost.get() + freed.size),
+        }
+    }
+}
+
+impl OutOfLineAllocator {
+    /// Crée un allocateur vide ; `init` lui confie son tas et ses descripteurs.
+    pub const fn new() -> Self {
+        OutOfLineAllocator {
+            ranges: Spinlock::new(null_mut()),
+            capacity: Cell::new(0),
+            len: Cell::new(0),
+            lost: Cell::new(0),
+        }
+    }
+
+    /// # Safety
+    /// Confie le tas `heap_start..heap_start + heap_size` à l'allocateur, qui range ses plages
+    /// libres dans les `capacity` descripteurs à partir de `metadata`. Les deux zones doivent
+    /// être valides, inutilisées et disjointes, et `metadata` aligné pour `FreeRange` ; un
+    /// nouvel appel abandonne toutes les allocations en cours.
+    pub unsafe fn init(&self, heap_start: usize, heap_size: usize, metadata: *mut FreeRange, capacity: usize) {
+        let _guard = self.ranges.lock();
+        *self.ranges.get() = metadata;
+        self.capacity.set(capacity);
+        self.len.set(0);
+        self.lost.set(0);
+
+        if heap_size > 0 && capacity > 0 {
+            metadata.write(FreeRange { start: heap_start, size: heap_size });
+            self.len.set(1);
+        }
+    }
+
+    /// Retourne le nombre de plages libres, donc de descripteurs utilisés.
+    pub fn free_range_count(&self) -> usize {
+        let _guard = self.ranges.lock();
+        self.len.get()
+    }
+
+    /// Retourne le nombre total d'octets libres.
+    pub fn free_bytes(&self) -> usize {
+        let _guard = self.ranges.lock();
+        let ranges = unsafe { *self.ranges.get() };
+        (0..self.len.get()).map(|i| unsafe { (*ranges.add(i)).size }).sum()
+    }
+
+    /// Retourne le nombre d'octets libérés alors que tous les descripteurs étaient utilisés :
+    /// ils ne seront plus jamais attribués.
+    pub fn lost_bytes(&self) -> usize {
+        let _guard = self.ranges.lock();
+        self.lost.get()
+    }
+
+    /// Insère `range` à la position `index` en décalant les suivantes. Le verrou doit être
+    /// détenu et un descripteur libre disponible.
+    unsafe fn insert(&self, index: usize, range: FreeRange) {
+        let ranges = *self.ranges.get();
+        ptr::copy(ranges.add(index), ranges.add(index + 1), self.len.get() - index);
+        ranges.add(index).write(range);
+        self.len.set(self.len.get() + 1);
+    }
+
+    /// Retire la plage à la position `index` en décalant les suivantes. Le verrou doit être
+    /// détenu.
+    unsafe fn remove(&self, index: usize) {
+        let ranges = *self.ranges.get();
+        ptr::copy(ranges.add(index + 1), ranges.add(index), self.len.get() - index - 1);
+        self.len.set(self.len.get() - 1);
+    }
+}
@@ -0,0 +1,153 @@
+use core::cell::Cell;
+use core::mem;
+use core::ptr::{null_mut, NonNull};
+
+use crate::spinlock::Spinlock;
+
+// Sous-allocateur d'emplacements de taille fixe.
+
+// La région confiée à `init` commence par une table de bits (un bit par emplacement,
+// à 1 si l'emplacement est occupé) suivie des emplacements eux-mêmes, alignés sur
+// `ALIGN` : aucun en-tête n'accompagne les objets.
+
+const BITS: usize = u64::BITS as usize;
+
+pub struct Slab<const SIZE: usize, const ALIGN: usize> {
+    bitmap: Spinlock<*mut u64>, // Table d'occupation ; le verrou protège tout l'état
+    slots: Cell<usize>,         // Adresse du premier emplacement
+    capacity: Cell<usize>,      // Nombre d'emplacements
+    free: Cell<usize>,          // Nombre d'emplacements libres
+    hint: Cell<usize>,          // Mot de la table où reprendre la recherche
+}
+
+/// # Safety
+/// Toutes les modifications de la table et des champs se font sous le verrou de `bitmap`.
+unsafe impl<const SIZE: usize, const ALIGN: usize> Sync for Slab<SIZE, ALIGN> {}
+
+impl<const SIZE: usize, const ALIGN: usize> Default for Slab<SIZE, ALIGN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize, const ALIGN: usize> Slab<SIZE, ALIGN> {
+    /// Écart entre deux emplacements : `SIZE` arrondi à `ALIGN`.
+    pub const STRIDE: usize = {
+        assert!(ALIGN.is_power_of_two(), "ALIGN doit être une puissance de deux");
+        let size = if SIZE == 0 { 1 } else { SIZE };
+        (size + ALIGN - 1) & !(ALIGN - 1)
+    };
+
+    /// Crée un slab vide ; `init` lui confie sa région.
+    pub const fn new() -> Self {
+        Slab {
+            bitmap: Spinlock::new(null_mut()),
+            slots: Cell::new(0),
+            capacity: Cell::new(0),
+            free: Cell::new(0),
+            hint: Cell::new(0),
+        }
+    }
+
+    /// # Safety
+    /// Confie la région `start..start + size` au slab et la découpe en autant d'emplacements
+    /// que possible après la table d'occupation. La région doit être valide, inutilisée et
+    /// alignée sur 8 octets ; un nouvel appel abandonne tous les emplacements en cours.
+    pub unsafe fn init(&self, start: usize, size: usize) {
+        let _guard = self.bitmap.lock();
+
+        let mut capacity = size / Self::STRIDE;
+        while capacity > 0 && Self::slots_offset(start, capacity) + capacity * Self::STRIDE > size {
+            capacity -= 1;
+        }
+
+        let words = capacity.div_ceil(BITS);
+        let bitmap = start as *mut u64;
+        for i in 0..words {
+            bitmap.add(i).write(0);
+        }
+
+        // Les bits au-delà du dernier emplacement restent occupés pour ne jamais être attribués
+        if !capacity.is_multiple_of(BITS) {
+            *bitmap.add(words - 1) = !0 << (capacity % BITS);
+        }
+
+        *self.bitmap.get() = bitmap;
+        self.slots.set(start + Self::slots_offset(start, capacity));
+        self.capacity.set(capacity);
+        self.free.set(capacity);
+        self.hint.set(0);
+    }
+
+    /// Attribue un emplacement libre de `SIZE` octets aligné sur `ALIGN`, ou `None` si
+    /// tous sont occupés.
+    pub fn alloc_slot(&self) -> Option<NonNull<u8>> {
+        let _guard = self.bitmap.lock();
+        if self.free.get() == 0 {
+            return None;
+        }
+
+        let bitmap = unsafe { *self.bitmap.get() };
+        let words = self.capacity.get().div_ceil(BITS);
+
+        // La recherche part du dernier mot libéré : un emplacement y est presque toujours libre
+        for offset in 0..words {
+            let word_index = (self.hint.get() + offset) % words;
+            let word = unsafe { bitmap.add(word_index) };
+            let bits = unsafe { *word };
+
+            if bits != !0 {
+                let bit = (!bits).trailing_zeros() as usize;
+                unsafe { *word = bits | (1 << bit) };
+                self.free.set(self.free.get() - 1);
+                self.hint.set(word_index);
+
+                let addr = self.slots.get() + (word_index * BITS + bit) * Self::STRIDE;
+                return NonNull::new(addr as *mut u8);
+            }
+        }
+
+        None
+    }
+
+    /// # Safety
+    /// Rend l'emplacement `ptr`, qui doit avoir été attribué par `alloc_slot` de ce slab.
+    pub unsafe fn free_slot(&self, ptr: *mut u8) {
+        let _guard = self.bitmap.lock();
+
+        let offset = ptr as usize - self.slots.get();
+        debug_assert!(offset.is_multiple_of(Self::STRIDE), "pointeur hors d'un emplacement : {:p}", ptr);
+
+        let index = offset / Self::STRIDE;
+        let word = (*self.bitmap.get()).add(index / BITS);
+        let mask = 1 << (index % BITS);
+
+        #[cfg(feature = "debug_checks")]
+        if *word & mask == 0 {
+            panic!("double libération détectée à l'adresse {:#x}", ptr as usize);
+        }
+
+        *word &= !mask;
+        self.free.set(self.free.get() + 1);
+        self.hint.set(index / BITS);
+    }
+
+    /// Retourne le nombre total d'emplacements.
+    pub fn capacity(&self) -> usize {
+        let _guard = self.bitmap.lock();
+        self.capacity.get()
+    }
+
+    /// Retourne le nombre d'emplacements libres.
+    pub fn free_slots(&self) -> usize {
+        let _guard = self.bitmap.lock();
+        self.free.get()
+    }
+
+    /// Retourne la position du premier emplacement par rapport à `start`, après une table
+    /// d'occupation de `capacity` bits.
+    fn slots_offset(start: usize, capacity: usize) -> usize {
+        let table_end = start + capacity.div_ceil(BITS) * mem::size_of::<u64>();
+        ((table_end + ALIGN - 1) & !(ALIGN - 1)) - start
+    }
+}
@@ -0,0 +1,54 @@
+use core::cell::UnsafeCell;
+use core::hint::spin_loop;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Verrou tournant minimal, utilisable sans bibliothèque standard.
+
+// La valeur protégée reste accessible par pointeur brut via `get` : c'est à
+// l'appelant de s'assurer qu'il détient le verrou avant d'y toucher.
+
+pub struct Spinlock<T> {
+    locked: AtomicBool,   // Vrai tant qu'un garde est actif
+    value: UnsafeCell<T>, // Valeur protégée par le verrou
+}
+
+/// Garde rendant le verrou lorsqu'il est détruit, quel que soit le chemin de sortie.
+pub struct SpinlockGuard<'a, T> {
+    lock: &'a Spinlock<T>,
+}
+
+impl<T> Spinlock<T> {
+    /// Crée un verrou libre contenant `value`.
+    pub const fn new(value: T) -> Self {
+        Spinlock {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Attend que le verrou soit libre puis le prend.
+    pub fn lock(&self) -> SpinlockGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.locked.load(Ordering::Relaxed) {
+                spin_loop();
+            }
+        }
+
+        SpinlockGuard { lock: self }
+    }
+
+    /// Retourne un pointeur brut vers la valeur protégée.
+    pub fn get(&self) -> *mut T {
+        self.value.get()
+    }
+}
+
+impl<T> Drop for SpinlockGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,92 @@
+use core::alloc::{GlobalAlloc, Layout};
+use core::ptr::null_mut;
+
+use crate::FreeListAllocator;
+
+// Rejeu de charges de travail déterministes.
+
+// Une suite d'opérations est rejouée telle quelle contre un allocateur, ce qui permet de
+// comparer les stratégies sur exactement les mêmes requêtes.
+
+/// Nombre maximal d'allocations suivies au cours d'un rejeu.
+pub const MAX_WORKLOAD_ALLOCS: usize = 256;
+
+/// Opération d'une charge de travail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    /// Allocation de `size` octets alignés sur `align`.
+    Alloc { size: usize, align: usize },
+    /// Libération de la `index`-ième allocation de la charge (en comptant à partir de 0,
+    /// allocations échouées comprises).
+    Free { index: usize },
+}
+
+/// Résultat d'un rejeu.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkloadStats {
+    /// Nombre d'allocations réussies.
+    pub successes: usize,
+    /// Nombre d'allocations échouées, layout invalide compris.
+    pub failures: usize,
+    /// Nombre d'allocations non tentées car au-delà de `MAX_WORKLOAD_ALLOCS`.
+    pub skipped: usize,
+    /// Plus grand nombre d'octets alloués atteint pendant le rejeu.
+    pub peak_used: usize,
+}
+
+impl<const MIN: usize> FreeListAllocator<MIN> {
+    /// Rejoue `ops` dans l'ordre puis libère les allocations restantes, de sorte que
+    /// l'allocateur retrouve son état de départ. Une libération d'une allocation échouée,
+    /// déjà libérée ou inexistante est ignorée ; les allocations au-delà de
+    /// `MAX_WORKLOAD_ALLOCS` ne sont pas tentées et sont comptées dans `skipped`.
+    pub fn run_workload(&self, ops: &[Op]) -> WorkloadStats {
+        let mut allocations = [(null_mut::<u8>(), Layout::new::<u8>()); MAX_WORKLOAD_ALLOCS];
+        let mut count = 0;
+        let mut result = WorkloadStats::default();
+        let base = self.stats().allocated_bytes;
+
+        for op in ops {
+            match *op {
+                Op::Alloc { size, align } => {
+                    // Une allocation non suivie ne pourrait pas être libérée à la fin
+                    if count == MAX_WORKLOAD_ALLOCS {
+                        result.skipped += 1;
+                        continue;
+                    }
+
+                    // Un layout invalide occupe tout de même son indice
+                    let (ptr, layout) = match Layout::from_size_align(size, align) {
+                        Ok(layout) => (unsafe { self.alloc(layout) }, layout),
+                        Err(_) => (null_mut(), Layout::new::<u8>()),
+                    };
+                    allocations[count] = (ptr, layout);
+                    count += 1;
+
+                    if ptr.is_null() {
+                        result.failures += 1;
+                    } else {
+                        result.successes += 1;
+                        let used = self.stats().allocated_bytes - base;
+                        result.peak_used = result.peak_used.max(used);
+                    }
+                }
+                Op::Free { index } => {
+                    if let Some((ptr, layout)) = allocations[..count].get_mut(index) {
+                        if !ptr.is_null() {
+                            unsafe { self.dealloc(*ptr, *layout) };
+                            *ptr = null_mut();
+                        }
+                    }
+                }
+            }
+        }
+
+        for &(ptr, layout) in &allocations[..count] {
+            if !ptr.is_null() {
+                unsafe { self.dealloc(ptr, layout) };
+            }
+        }
+
+        result
+    }
+}
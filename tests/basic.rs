@@ -1,11 +1,36 @@
-use allocateurharjit::ALLOCATOR;
-use core::alloc::Layout;
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use allocateurharjit::{AllocError, FreeListAllocator, InitError, Op, RestoreError, StaticHeap, Strategy};
+use core::alloc::{GlobalAlloc, Layout};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+// Instance globale, comme celle que déclare le binaire
+static ALLOCATOR: FreeListAllocator = FreeListAllocator::new();
+
+// `ALLOCATOR` est partagé par tous les tests : on les exécute un à la fois.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    SERIAL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Un tableau `[u8; N]` n'est aligné que sur 1 octet : sans cet enrobage,
+// `insert_free_region` rejette le tas.
+#[repr(C, align(16))]
+struct Heap([u8; 1024]);
+
+// Les tests exclus des modes `header`, `debug_tags` et `canaries` vérifient des adresses et
+// des tailles exactes, calculées pour les blocs nus : l'en-tête de taille ou d'étiquette décale
+// chaque pointeur et agrandit chaque bloc, le canari agrandit chaque bloc.
 
 #[test]
 fn test_allocator() {
+    let _guard = serial();
     unsafe {
-        static mut HEAP: [u8; 1024] = [0; 1024];
-        ALLOCATOR.init(HEAP.as_ptr() as usize, HEAP.len());
+        static HEAP: StaticHeap<1024> = StaticHeap::new();
+        let (heap_start, heap_size) = HEAP.as_region();
+        ALLOCATOR.init(heap_start, heap_size);
 
         let layout = Layout::from_size_align(16, 8).unwrap();
         let ptr = ALLOCATOR.alloc(layout);
@@ -14,3 +39,2472 @@ fn test_allocator() {
         ALLOCATOR.dealloc(ptr, layout);
     }
 }
+
+#[test]
+fn test_alloc_respecte_alignement() {
+    let _guard = serial();
+    unsafe {
+        static HEAP: StaticHeap<1024> = StaticHeap::new();
+        let (heap_start, heap_size) = HEAP.as_region();
+        ALLOCATOR.init(heap_start, heap_size);
+
+        let layout = Layout::from_size_align(8, 64).unwrap();
+        let ptr = ALLOCATOR.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation a échoué.");
+        assert_eq!(ptr as usize % 64, 0, "Le pointeur n'est pas aligné sur 64 octets.");
+
+        ALLOCATOR.dealloc(ptr, layout);
+    }
+}
+
+#[test]
+fn test_plusieurs_petites_allocations() {
+    let _guard = serial();
+    unsafe {
+        static HEAP: StaticHeap<1024> = StaticHeap::new();
+        let (heap_start, heap_size) = HEAP.as_region();
+        ALLOCATOR.init(heap_start, heap_size);
+
+        // Sans découpage, la première allocation consommerait tout le tas.
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let mut ptrs = [core::ptr::null_mut(); 8];
+        for ptr in ptrs.iter_mut() {
+            *ptr = ALLOCATOR.alloc(layout);
+            assert!(!ptr.is_null(), "L'allocation a échoué.");
+        }
+
+        for (i, a) in ptrs.iter().enumerate() {
+            for b in &ptrs[i + 1..] {
+                assert_ne!(a, b, "Deux allocations partagent la même adresse.");
+            }
+        }
+
+        for ptr in ptrs {
+            ALLOCATOR.dealloc(ptr, layout);
+        }
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_fusion_blocs_adjacents() {
+    let _guard = serial();
+    unsafe {
+        // Plus grand que les tas des autres tests : seul ce tas peut servir la dernière requête.
+        static HEAP: StaticHeap<4096> = StaticHeap::new();
+        let (heap_start, heap_size) = HEAP.as_region();
+        ALLOCATOR.init(heap_start, heap_size);
+
+        let half = Layout::from_size_align(2048, 8).unwrap();
+        let a = ALLOCATOR.alloc(half);
+        let b = ALLOCATOR.alloc(half);
+        assert!(!a.is_null() && !b.is_null(), "L'allocation a échoué.");
+        assert_eq!(a as usize + 2048, b as usize, "Les allocations ne sont pas adjacentes.");
+
+        ALLOCATOR.dealloc(a, half);
+        ALLOCATOR.dealloc(b, half);
+
+        // Les deux moitiés libérées doivent former un seul bloc de 4096 octets.
+        let whole = Layout::from_size_align(4096, 8).unwrap();
+        let ptr = ALLOCATOR.alloc(whole);
+        assert_eq!(ptr, a, "Les blocs adjacents n'ont pas été fusionnés.");
+
+        ALLOCATOR.dealloc(ptr, whole);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_best_fit_preserve_le_grand_bloc() {
+    let mut first_heap = Heap([0; 1024]);
+    let mut best_heap = Heap([0; 1024]);
+    let first_fit = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let best_fit = FreeListAllocator::with_strategy(Strategy::BestFit);
+
+    unsafe {
+        // Même liste fragmentée pour les deux : un grand bloc suivi d'un petit, de la même classe.
+        for (allocator, heap) in [(&first_fit, &mut first_heap), (&best_fit, &mut best_heap)] {
+            let base = heap.0.as_mut_ptr() as usize;
+            allocator.insert_free_region(base, 448);
+            allocator.insert_free_region(base + 512, 272);
+        }
+
+        let small = Layout::from_size_align(256, 8).unwrap();
+        assert!(!first_fit.alloc(small).is_null(), "L'allocation a échoué.");
+        assert!(!best_fit.alloc(small).is_null(), "L'allocation a échoué.");
+
+        // Seul best-fit a laissé le bloc de 448 octets intact.
+        let large = Layout::from_size_align(448, 8).unwrap();
+        assert!(first_fit.alloc(large).is_null(), "First-fit aurait dû entamer le grand bloc.");
+        assert!(!best_fit.alloc(large).is_null(), "Best-fit a entamé le grand bloc.");
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_next_fit_avance_le_curseur() {
+    let mut first_heap = Heap([0; 1024]);
+    let mut next_heap = Heap([0; 1024]);
+    let first_fit = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let next_fit = FreeListAllocator::with_strategy(Strategy::NextFit);
+    let layout = Layout::from_size_align(128, 8).unwrap();
+
+    unsafe {
+        let mut results = [core::ptr::null_mut(); 2];
+
+        for (i, (allocator, heap)) in [(&first_fit, &mut first_heap), (&next_fit, &mut next_heap)]
+            .into_iter()
+            .enumerate()
+        {
+            let base = heap.0.as_mut_ptr() as usize;
+            allocator.insert_free_region(base, 128);
+            allocator.insert_free_region(base + 256, 128);
+            allocator.insert_free_region(base + 512, 128);
+
+            // Le premier bloc est pris, puis la recherche passe au second.
+            let x = allocator.alloc(layout);
+            let y = allocator.alloc(layout);
+            assert_eq!(x as usize, base);
+            assert_eq!(y as usize, base + 256);
+
+            // Le premier bloc redevient entièrement libre.
+            allocator.dealloc(x, layout);
+            results[i] = allocator.alloc(layout);
+        }
+
+        let first_base = first_heap.0.as_mut_ptr() as usize;
+        let next_base = next_heap.0.as_mut_ptr() as usize;
+        assert_eq!(results[0] as usize, first_base, "First-fit doit repartir du premier bloc.");
+        assert_eq!(results[1] as usize, next_base + 512, "Next-fit doit reprendre après le curseur.");
+    }
+}
+
+#[test]
+fn test_realloc_agrandit_sur_place() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation a échoué.");
+
+        // Le bloc libre qui suit est absorbé : le pointeur ne change pas.
+        let grown = allocator.realloc(ptr, layout, 256);
+        assert_eq!(grown, ptr, "L'agrandissement aurait dû se faire sur place.");
+
+        allocator.dealloc(grown, Layout::from_size_align(256, 8).unwrap());
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_realloc_reduit_et_libere_la_fin() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let layout = Layout::from_size_align(512, 8).unwrap();
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation a échoué.");
+
+        let shrunk = allocator.realloc(ptr, layout, 64);
+        assert_eq!(shrunk, ptr, "La réduction aurait dû se faire sur place.");
+
+        // La fin libérée est réutilisée par l'allocation suivante.
+        let next = allocator.alloc(Layout::from_size_align(128, 8).unwrap());
+        assert_eq!(next as usize, ptr as usize + 64, "La fin du bloc n'a pas été libérée.");
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_realloc_reduit_le_tas_entier() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let whole = Layout::from_size_align(1024, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let ptr = allocator.alloc(whole);
+        assert!(allocator.is_exhausted(), "Tout le tas devrait être alloué.");
+
+        let shrunk = allocator.realloc(ptr, whole, 128);
+        assert_eq!(shrunk, ptr, "La réduction aurait dû se faire sur place.");
+
+        // Les 896 octets libérés forment un seul bloc, entièrement réutilisable.
+        let tail = allocator.alloc(Layout::from_size_align(896, 8).unwrap());
+        assert_eq!(tail as usize, ptr as usize + 128);
+        assert!(allocator.is_exhausted());
+    }
+}
+
+#[test]
+fn test_grow_zeroed_n_efface_que_la_fin() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let small = Layout::from_size_align(64, 8).unwrap();
+    let scratch = Layout::from_size_align(192, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        // Sur place : le voisin libéré contient encore des octets non nuls.
+        let a = allocator.alloc(small);
+        let b = allocator.alloc(scratch);
+        core::ptr::write_bytes(a, 0xAB, 64);
+        core::ptr::write_bytes(b, 0xFF, 192);
+        allocator.dealloc(b, scratch);
+
+        let grown = allocator.grow_zeroed(a, small, 256);
+        assert_eq!(grown, a, "Le bloc aurait dû grandir sur place.");
+        let bytes = core::slice::from_raw_parts(grown, 256);
+        assert!(bytes[..64].iter().all(|&b| b == 0xAB), "Le contenu d'origine a été modifié.");
+        assert!(bytes[64..].iter().all(|&b| b == 0), "La fin ajoutée n'a pas été mise à zéro.");
+
+        // Par copie : le voisin est occupé.
+        let c = allocator.alloc(small);
+        let d = allocator.alloc(small);
+        core::ptr::write_bytes(c, 0xCD, 64);
+
+        let moved = allocator.grow_zeroed(c, small, 128);
+        assert_ne!(moved, c, "Le bloc aurait dû être déplacé.");
+        let bytes = core::slice::from_raw_parts(moved, 128);
+        assert!(bytes[..64].iter().all(|&b| b == 0xCD), "Le contenu d'origine n'a pas été copié.");
+        assert!(bytes[64..].iter().all(|&b| b == 0), "La fin ajoutée n'a pas été mise à zéro.");
+
+        allocator.dealloc(d, small);
+    }
+}
+
+#[test]
+fn test_realloc_deplace_si_voisin_occupe() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let ptr = allocator.alloc(layout);
+        let neighbor = allocator.alloc(layout);
+        assert!(!ptr.is_null() && !neighbor.is_null(), "L'allocation a échoué.");
+
+        for i in 0..64 {
+            *ptr.add(i) = i as u8;
+        }
+
+        // Le voisin est occupé : le contenu doit être déplacé.
+        let moved = allocator.realloc(ptr, layout, 256);
+        assert!(!moved.is_null(), "La réallocation a échoué.");
+        assert_ne!(moved, ptr, "Le bloc aurait dû être déplacé.");
+        for i in 0..64 {
+            assert_eq!(*moved.add(i), i as u8, "Le contenu n'a pas été copié.");
+        }
+    }
+}
+
+#[test]
+fn test_alloc_zeroed_bloc_propre_et_bloc_ecrit() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init_clean(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        // Bloc jamais écrit : seul l'ancien en-tête doit être effacé.
+        let clean = allocator.alloc_zeroed(layout);
+        assert!(!clean.is_null(), "L'allocation a échoué.");
+        assert!((0..64).all(|i| *clean.add(i) == 0), "Le bloc propre n'est pas à zéro.");
+
+        // Bloc écrit puis libéré : il doit être entièrement remis à zéro.
+        clean.write_bytes(0xFF, 64);
+        allocator.dealloc(clean, layout);
+
+        let written = allocator.alloc_zeroed(layout);
+        assert_eq!(written, clean, "Le bloc libéré aurait dû être réutilisé.");
+        assert!((0..64).all(|i| *written.add(i) == 0), "Le bloc écrit n'a pas été remis à zéro.");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_acces_concurrents() {
+    static ALLOCATOR: FreeListAllocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    static HEAP: StaticHeap<1024> = StaticHeap::new();
+
+    unsafe {
+        let (base, heap_size) = HEAP.as_region();
+        ALLOCATOR.init(base, heap_size);
+
+        let threads: Vec<_> = (0..4u8)
+            .map(|id| {
+                std::thread::spawn(move || {
+                    let layout = Layout::from_size_align(32, 8).unwrap();
+                    for _ in 0..10_000 {
+                        let ptr = ALLOCATOR.alloc(layout);
+                        assert!(!ptr.is_null(), "L'allocation a échoué.");
+                        ptr.write_bytes(id, 32);
+                        assert!((0..32).all(|i| *ptr.add(i) == id), "Bloc partagé entre deux threads.");
+                        ALLOCATOR.dealloc(ptr, layout);
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        // Tout a été libéré : la liste doit s'être reformée en un seul bloc.
+        let whole = Layout::from_size_align(1024, 8).unwrap();
+        assert_eq!(ALLOCATOR.alloc(whole) as usize, base, "La liste des blocs libres est incohérente.");
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_statistiques() {
+    use allocateurharjit::AllocStats;
+
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let ptrs = [allocator.alloc(layout), allocator.alloc(layout), allocator.alloc(layout)];
+        assert!(ptrs.iter().all(|ptr| !ptr.is_null()), "L'allocation a échoué.");
+        allocator.dealloc(ptrs[1], layout);
+
+        assert_eq!(
+            allocator.stats(),
+            AllocStats {
+                allocated_bytes: 128,
+                free_bytes: 1024 - 128,
+                allocation_count: 3,
+                dealloc_count: 1,
+                peak_allocated: 192,
+            }
+        );
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_pic_d_utilisation() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let large = Layout::from_size_align(256, 8).unwrap();
+    let small = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert_eq!(allocator.peak_used(), 0);
+
+        let a = allocator.alloc(large);
+        let b = allocator.alloc(large);
+        allocator.dealloc(a, large);
+        allocator.dealloc(b, large);
+
+        // Le pic garde la trace des 512 octets alloués simultanément.
+        let c = allocator.alloc(small);
+        assert_eq!(allocator.stats().allocated_bytes, 64);
+        assert_eq!(allocator.peak_used(), 512);
+        allocator.dealloc(c, small);
+    }
+}
+
+#[test]
+fn test_allocate_retourne_la_place_utilisable() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(100, 16).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+    }
+
+    let block = allocator.allocate(layout).expect("L'allocation a échoué.");
+    let (ptr, len) = (block.as_ptr() as *mut u8, block.len());
+    assert!(len >= layout.size(), "La tranche est plus courte que la requête.");
+    assert_eq!(ptr as usize % layout.align(), 0, "La tranche n'est pas alignée.");
+
+    unsafe {
+        // Tout l'excédent est utilisable.
+        core::ptr::write_bytes(ptr, 0x5A, len);
+        allocator.dealloc(ptr, Layout::from_size_align(len, layout.align()).unwrap());
+    }
+    assert_eq!(allocator.stats().allocated_bytes, 0);
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_reserve_de_secours() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        allocator.set_reserve(128);
+
+        // Le tas ordinaire s'épuise sans jamais entamer la réserve.
+        let mut count = 0;
+        while !allocator.alloc(layout).is_null() {
+            count += 1;
+        }
+        assert_eq!(count, (1024 - 128) / 64);
+        assert!(allocator.alloc(layout).is_null());
+
+        let ptr = allocator.alloc_from_reserve(layout);
+        assert!(!ptr.is_null(), "La réserve aurait dû satisfaire la requête.");
+        assert!(heap.0.as_ptr_range().contains(&(ptr as *const u8)));
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_parcours_des_blocs_libres() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        let base = heap.0.as_mut_ptr() as usize;
+        allocator.init(base, heap.0.len());
+
+        let mut blocks = allocator.free_blocks();
+        assert_eq!(blocks.next(), Some((base, 1024)));
+        assert_eq!(blocks.next(), None);
+
+        // Libérer le premier de deux blocs découpés laisse deux régions libres.
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        assert!(!a.is_null() && !b.is_null(), "L'allocation a échoué.");
+        allocator.dealloc(a, layout);
+
+        let mut blocks = allocator.free_blocks();
+        assert_eq!(blocks.next(), Some((base, 64)));
+        assert_eq!(blocks.next(), Some((base + 128, 1024 - 128)));
+        assert_eq!(blocks.next(), None);
+    }
+}
+
+#[test]
+fn test_requete_plus_grande_que_le_tas() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert_eq!(allocator.max_possible_alloc(), 1024);
+
+        let ptr = allocator.alloc(Layout::from_size_align(2048, 8).unwrap());
+        assert!(ptr.is_null(), "Une requête plus grande que le tas doit échouer.");
+    }
+}
+
+#[test]
+fn test_gestionnaire_oom() {
+    static OOM_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    fn on_oom(_layout: Layout) {
+        OOM_COUNT.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    allocator.set_oom_handler(on_oom);
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        assert!(allocator.alloc(Layout::from_size_align(2048, 8).unwrap()).is_null());
+        assert_eq!(OOM_COUNT.load(Ordering::SeqCst), 1, "Le gestionnaire aurait dû être appelé.");
+
+        assert!(!allocator.alloc(Layout::from_size_align(64, 8).unwrap()).is_null());
+        assert_eq!(OOM_COUNT.load(Ordering::SeqCst), 1, "Le gestionnaire a été appelé à tort.");
+    }
+}
+
+#[cfg(feature = "debug_checks")]
+#[test]
+fn test_double_liberation_detectee() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation a échoué.");
+
+        // La seconde libération est refusée sans toucher aux listes.
+        allocator.dealloc(ptr, layout);
+        allocator.dealloc(ptr, layout);
+        assert_eq!(allocator.total_free(), heap.0.len());
+        assert_eq!(allocator.count_free_blocks(), 1);
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[cfg(feature = "debug_checks")]
+#[test]
+fn test_liberation_verifiee() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    let mut local = [0u64; 8];
+
+    unsafe {
+        use allocateurharjit::DeallocError;
+
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        let ptr = allocator.alloc(layout);
+
+        assert_eq!(allocator.try_dealloc(local.as_mut_ptr() as *mut u8, layout), Err(DeallocError::NotOwned));
+        assert_eq!(allocator.try_dealloc(ptr, layout), Ok(()));
+        assert_eq!(allocator.try_dealloc(ptr, layout), Err(DeallocError::DoubleFree));
+        assert_eq!(allocator.total_free(), heap.0.len());
+    }
+}
+
+#[cfg(feature = "debug_checks")]
+#[test]
+fn test_double_liberation_dans_une_case_rapide() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new().with_small_bins(true);
+    let layout = Layout::from_size_align(32, 8).unwrap();
+
+    unsafe {
+        use allocateurharjit::DeallocError;
+
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        let ptr = allocator.alloc(layout);
+
+        // Le bloc libéré attend dans sa case rapide, hors des listes libres.
+        assert_eq!(allocator.try_dealloc(ptr, layout), Ok(()));
+        assert_eq!(allocator.try_dealloc(ptr, layout), Err(DeallocError::DoubleFree));
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[cfg(feature = "debug_checks")]
+#[test]
+#[should_panic(expected = "chevauchement détecté")]
+fn test_chevauchement_de_regions_detecte() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        allocator.insert_free_region(base + 128, 256);
+
+        // La nouvelle région commence avant le bloc libre mais le recouvre en partie.
+        allocator.insert_free_region(base, 256);
+    }
+}
+
+#[cfg(feature = "debug_checks")]
+#[test]
+fn test_liberation_d_un_pointeur_etranger_ignoree() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    let mut local = [0u64; 8];
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        let ptr = allocator.alloc(layout);
+        let free_before = allocator.total_free();
+
+        // Un pointeur de la pile n'appartient à aucune région du tas.
+        allocator.dealloc(local.as_mut_ptr() as *mut u8, layout);
+        assert_eq!(allocator.total_free(), free_before, "Le pointeur étranger a été inséré dans la liste libre.");
+        assert_eq!(allocator.count_free_blocks(), 1);
+        assert_eq!(allocator.validate(), Ok(()));
+
+        allocator.dealloc(ptr, layout);
+        assert_eq!(allocator.total_free(), heap.0.len());
+    }
+}
+
+#[cfg(feature = "canaries")]
+#[test]
+#[should_panic(expected = "canari corrompu")]
+fn test_canari_detecte_le_debordement() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let layout = Layout::from_size_align(20, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation a échoué.");
+
+        // Un octet écrit au-delà des 20 octets demandés.
+        *ptr.add(20) = 0;
+        allocator.dealloc(ptr, layout);
+    }
+}
+
+#[cfg(feature = "poison")]
+#[test]
+fn test_memoire_liberee_empoisonnee() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        assert!(!a.is_null() && !b.is_null(), "L'allocation a échoué.");
+
+        a.write_bytes(0x11, 64);
+        allocator.dealloc(a, layout);
+
+        // `b` empêche toute fusion : le premier bloc libre est exactement `a`.
+        let (addr, size) = allocator.free_blocks().next().unwrap();
+        assert_eq!(addr, a as usize);
+
+        // Au-delà de l'en-tête du bloc libre, tout est empoisonné.
+        let payload = core::slice::from_raw_parts((addr + 32) as *const u8, size - 32);
+        assert!(payload.iter().all(|&byte| byte == 0xAB), "La mémoire libérée n'est pas empoisonnée.");
+
+        allocator.dealloc(b, layout);
+    }
+}
+
+#[test]
+fn test_init_zeroed_efface_le_tas() {
+    let mut heap = Heap([0x5A; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::FirstFit);
+
+    unsafe {
+        allocator.init_zeroed(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        // Le bloc est propre : `alloc_zeroed` n'efface que l'ancien en-tête,
+        // le reste doit déjà avoir été remis à zéro par `init_zeroed`.
+        let ptr = allocator.alloc_zeroed(Layout::from_size_align(256, 8).unwrap());
+        assert!(!ptr.is_null(), "L'allocation a échoué.");
+        assert!((0..256).all(|i| *ptr.add(i) == 0), "Le tas n'a pas été remis à zéro.");
+    }
+}
+
+#[test]
+fn test_init_clean_aligne_un_tas_mal_aligne() {
+    use allocateurharjit::HEAP_ALIGN;
+
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let start = (base + 3).next_multiple_of(HEAP_ALIGN);
+    let size = ((base + 3 + 1000) & !(HEAP_ALIGN - 1)) - start;
+
+    // Les deux variantes réduisent la région comme `init`.
+    let clean = FreeListAllocator::new();
+    let zeroed = FreeListAllocator::new();
+    unsafe {
+        clean.init_clean(base + 3, 1000);
+        assert_eq!(clean.free_blocks().next(), Some((start, size)));
+        zeroed.init_zeroed(base + 3, 1000);
+        assert_eq!(zeroed.free_blocks().next(), Some((start, size)));
+        assert_eq!(zeroed.validate(), Ok(()));
+    }
+}
+
+// Un crate utilisateur doit pouvoir déclarer sa propre instance statique.
+static USER_ALLOCATOR: FreeListAllocator = FreeListAllocator::new();
+
+#[test]
+fn test_instance_statique_utilisateur() {
+    unsafe {
+        static HEAP: StaticHeap<1024> = StaticHeap::new();
+        let (heap_start, heap_size) = HEAP.as_region();
+        USER_ALLOCATOR.init(heap_start, heap_size);
+
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let ptr = USER_ALLOCATOR.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation a échoué.");
+        USER_ALLOCATOR.dealloc(ptr, layout);
+    }
+}
+
+#[test]
+fn test_plusieurs_regions() {
+    let mut first = Heap([0; 1024]);
+    let mut second = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(768, 8).unwrap();
+
+    unsafe {
+        let first_range = first.0.as_mut_ptr_range();
+        let second_range = second.0.as_mut_ptr_range();
+        allocator.init(first_range.start as usize, 1024);
+        allocator.add_region(second_range.start as usize, 1024);
+
+        // Chaque région ne peut contenir qu'une de ces allocations.
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        assert!(!a.is_null() && !b.is_null(), "L'allocation a échoué.");
+        assert!(allocator.alloc(layout).is_null(), "Les deux régions devraient être pleines.");
+
+        let in_first = |ptr: *mut u8| first_range.contains(&ptr);
+        let in_second = |ptr: *mut u8| second_range.contains(&ptr);
+        assert!(in_first(a) != in_first(b), "Les allocations devraient venir de régions différentes.");
+        assert!(in_second(a) || in_second(b), "Aucune allocation ne vient de la seconde région.");
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_regions_contigues_ne_fusionnent_pas() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(512, 8).unwrap();
+
+    unsafe {
+        // Deux banques placées bout à bout dans l'espace d'adressage.
+        allocator.init(base, 512);
+        allocator.add_region(base + 512, 512);
+        assert_eq!(allocator.count_free_blocks(), 2, "Les deux régions ont fusionné à l'initialisation.");
+
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        assert_eq!((a as usize, b as usize), (base, base + 512));
+
+        // La libération à la frontière ne fusionne pas les deux régions.
+        allocator.dealloc(b, layout);
+        allocator.dealloc(a, layout);
+        assert_eq!(allocator.count_free_blocks(), 2, "Les blocs ont fusionné par-dessus la frontière.");
+        assert_eq!(allocator.largest_free_block(), 512);
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_extension_du_tas() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        let base = heap.0.as_mut_ptr() as usize;
+        allocator.init(base, 512);
+
+        let half = Layout::from_size_align(512, 8).unwrap();
+        let small = Layout::from_size_align(64, 8).unwrap();
+        let a = allocator.alloc(half);
+        assert!(!a.is_null(), "L'allocation a échoué.");
+        assert!(allocator.alloc(small).is_null(), "Le tas devrait être épuisé.");
+
+        allocator.extend(base + 512, 512);
+        assert_eq!(allocator.max_possible_alloc(), 1024);
+        let b = allocator.alloc(small);
+        assert!(!b.is_null(), "Le tas agrandi devrait fournir de la mémoire.");
+
+        // La mémoire ajoutée prolonge la région : tout refusionne en un seul bloc.
+        allocator.dealloc(a, half);
+        allocator.dealloc(b, small);
+        let whole = allocator.alloc(Layout::from_size_align(1024, 8).unwrap());
+        assert_eq!(whole as usize, base, "La mémoire ajoutée n'a pas fusionné avec le tas.");
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_plus_grand_bloc_libre() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert_eq!(allocator.largest_free_block(), 1024);
+
+        let first = allocator.alloc(Layout::from_size_align(256, 8).unwrap());
+        let second = allocator.alloc(Layout::from_size_align(128, 8).unwrap());
+        assert!(!first.is_null() && !second.is_null(), "L'allocation a échoué.");
+
+        // Le premier bloc libéré est plus petit que le reste du tas.
+        allocator.dealloc(first, Layout::from_size_align(256, 8).unwrap());
+        assert_eq!(allocator.largest_free_block(), 1024 - 256 - 128);
+
+        assert!(!allocator.alloc(Layout::from_size_align(640, 8).unwrap()).is_null());
+        assert!(!allocator.alloc(Layout::from_size_align(256, 8).unwrap()).is_null());
+        assert_eq!(allocator.largest_free_block(), 0);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_total_libre() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert_eq!(allocator.total_free(), 1024);
+
+        // 100 octets sont arrondis à 104 pour respecter l'alignement des blocs.
+        let mut expected = 1024;
+        for (size, adjusted) in [(64, 64), (100, 104), (200, 200)] {
+            assert!(!allocator.alloc(Layout::from_size_align(size, 8).unwrap()).is_null());
+            expected -= adjusted;
+            assert_eq!(allocator.total_free(), expected);
+        }
+    }
+}
+
+#[test]
+fn test_nombre_de_blocs_libres() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        assert_eq!(allocator.count_free_blocks(), 0);
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert_eq!(allocator.count_free_blocks(), 1);
+
+        let blocks = [(); 4].map(|_| allocator.alloc(layout));
+
+        // Libérations non adjacentes : un bloc de plus à chaque fois.
+        allocator.dealloc(blocks[0], layout);
+        assert_eq!(allocator.count_free_blocks(), 2);
+        allocator.dealloc(blocks[2], layout);
+        assert_eq!(allocator.count_free_blocks(), 3);
+
+        // Le bloc du milieu relie ses deux voisins, le dernier rejoint la fin du tas.
+        allocator.dealloc(blocks[1], layout);
+        assert_eq!(allocator.count_free_blocks(), 2);
+        allocator.dealloc(blocks[3], layout);
+        assert_eq!(allocator.count_free_blocks(), 1);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_allocateur_epuise() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(1024, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert!(!allocator.is_exhausted(), "Un allocateur neuf ne doit pas être épuisé.");
+        assert!(allocator.has_free());
+
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation du tas entier a échoué.");
+        assert!(allocator.is_exhausted(), "Tout le tas est alloué.");
+        assert!(!allocator.has_free());
+
+        allocator.dealloc(ptr, layout);
+        assert!(allocator.has_free(), "La libération aurait dû rendre de la mémoire.");
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_reinitialisation() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let small = Layout::from_size_align(64, 8).unwrap();
+    let whole = Layout::from_size_align(1024, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        // On remplit le tas sans rien libérer.
+        while !allocator.alloc(small).is_null() {}
+        assert!(allocator.alloc(whole).is_null());
+
+        allocator.reset(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert_eq!(allocator.stats().allocated_bytes, 0);
+        assert_eq!(allocator.count_free_blocks(), 1);
+        assert!(!allocator.alloc(whole).is_null(), "Le tas entier devrait être disponible après reset.");
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_fragmentation() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(32, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        assert!(allocator.fragmentation() < 0.01, "Un tas intact ne doit pas être fragmenté.");
+
+        // On remplit le tas puis on libère un bloc sur deux : 16 trous de 32 octets.
+        let mut ptrs = [core::ptr::null_mut(); 32];
+        for ptr in ptrs.iter_mut() {
+            *ptr = allocator.alloc(layout);
+            assert!(!ptr.is_null(), "L'allocation a échoué.");
+        }
+        for ptr in ptrs.iter().step_by(2) {
+            allocator.dealloc(*ptr, layout);
+        }
+
+        assert!(allocator.fragmentation() > 0.9, "Le tas devrait être très fragmenté.");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_affichage_liste_libre() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let ptrs = [allocator.alloc(layout), allocator.alloc(layout), allocator.alloc(layout)];
+        allocator.dealloc(ptrs[0], layout);
+        allocator.dealloc(ptrs[2], layout);
+
+        let mut output = String::new();
+        allocator.dump(&mut output).unwrap();
+        assert_eq!(output.lines().count(), allocator.free_blocks().count());
+        assert_eq!(output.lines().count(), 2);
+    }
+}
+
+#[test]
+fn test_taille_minimale_personnalisee() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::<64>::configured(Strategy::FirstFit);
+    let tiny = Layout::from_size_align(1, 1).unwrap();
+
+    assert_eq!(FreeListAllocator::<64>::MIN_BLOCK_SIZE, 64);
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        // Chaque petite allocation est arrondie à 64 octets.
+        let a = allocator.alloc(tiny);
+        let b = allocator.alloc(tiny);
+        assert_eq!(b as usize - a as usize, 64, "La taille minimale n'a pas été respectée.");
+        assert_eq!(allocator.stats().allocated_bytes, 128);
+
+        allocator.dealloc(a, tiny);
+        allocator.dealloc(b, tiny);
+        assert_eq!(allocator.total_free(), 1024);
+    }
+}
+
+#[test]
+fn test_allocation_de_taille_nulle() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let empty = Layout::from_size_align(0, 16).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        let before = allocator.stats();
+
+        // Pointeur sentinelle aligné, le tas n'est pas touché.
+        let ptr = allocator.alloc(empty);
+        assert_eq!(ptr as usize, 16);
+        assert_eq!(allocator.stats(), before, "Une allocation de taille nulle a consommé de la mémoire.");
+
+        allocator.dealloc(ptr, empty);
+        assert_eq!(allocator.stats(), before, "Libérer une allocation de taille nulle a modifié le tas.");
+        assert_eq!(allocator.free_blocks().count(), 1);
+    }
+}
+
+#[test]
+fn test_try_alloc() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let ptr = allocator.try_alloc(layout).expect("L'allocation aurait dû réussir.");
+        assert!(heap.0.as_ptr_range().contains(&(ptr.as_ptr() as *const u8)));
+
+        let too_big = Layout::from_size_align(2048, 8).unwrap();
+        assert_eq!(allocator.try_alloc(too_big), Err(AllocError::OutOfMemory));
+
+        // Arrondie à l'alignement des blocs, la taille dépasserait `isize::MAX`
+        let invalid = Layout::from_size_align(isize::MAX as usize, 1).unwrap();
+        assert_eq!(allocator.try_alloc(invalid), Err(AllocError::InvalidLayout));
+        assert!(allocator.alloc(invalid).is_null());
+
+        allocator.dealloc(ptr.as_ptr(), layout);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_debut_saute_par_alignement_reinsere() {
+    let heap = StaticHeap::<2048>::new();
+    let (base, _) = heap.as_region();
+    let aligned_base = (base + 255) & !255;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(256, 256).unwrap();
+
+    unsafe {
+        // La région commence 64 octets après une frontière de 256 octets
+        allocator.insert_free_region(aligned_base + 64, 1024);
+
+        let ptr = allocator.alloc(layout);
+        assert_eq!(ptr as usize, aligned_base + 256);
+
+        // Les 192 octets sautés pour l'alignement restent disponibles
+        assert!(
+            allocator.free_blocks().any(|block| block == (aligned_base + 64, 192)),
+            "Le début sauté pour l'alignement a été perdu."
+        );
+        assert_eq!(allocator.total_free(), 1024 - 256);
+
+        allocator.dealloc(ptr, layout);
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(aligned_base + 64, 1024)]);
+    }
+}
+
+#[test]
+fn test_layout_pathologique_retourne_null() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+
+    // Arrondie à l'alignement des blocs, la taille dépasserait `isize::MAX`
+    let layout = Layout::from_size_align(isize::MAX as usize, 1).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        assert!(allocator.alloc(layout).is_null(), "Le layout aurait dû être refusé sans paniquer.");
+        assert!(allocator.alloc_zeroed(layout).is_null(), "Le layout aurait dû être refusé sans paniquer.");
+        assert_eq!(allocator.total_free(), 1024);
+    }
+}
+
+#[cfg(feature = "header")]
+#[test]
+fn test_entete_de_taille_ignore_le_layout() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(200, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        assert!(!a.is_null() && !b.is_null(), "L'allocation a échoué.");
+
+        // Layout volontairement faux : la taille est relue dans l'en-tête
+        let wrong = Layout::from_size_align(8, 8).unwrap();
+        allocator.dealloc(a, wrong);
+        allocator.dealloc(b, wrong);
+
+        assert_eq!(allocator.stats().allocated_bytes, 0);
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(heap.0.as_ptr() as usize, 1024)]);
+    }
+}
+
+#[cfg(feature = "debug_tags")]
+#[test]
+fn test_octets_vivants_par_etiquette() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let small = Layout::from_size_align(64, 8).unwrap();
+    let large = Layout::from_size_align(200, 8).unwrap();
+
+    let bytes_of = |tag: u32| {
+        allocator
+            .live_blocks_by_tag()
+            .iter()
+            .find(|&&(t, bytes)| t == tag && bytes > 0)
+            .map_or(0, |&(_, bytes)| bytes)
+    };
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+
+        let (a, b) = allocator.with_tag(1, || (allocator.alloc(small), allocator.alloc(small)));
+        let small_size = allocator.stats().allocated_bytes / 2;
+        let c = allocator.with_tag(2, || allocator.alloc(large));
+        let large_size = allocator.stats().allocated_bytes - 2 * small_size;
+
+        assert_eq!(bytes_of(1), 2 * small_size);
+        assert_eq!(bytes_of(2), large_size);
+
+        // Une allocation de l'étiquette 1 est libérée.
+        allocator.dealloc(a, small);
+        assert_eq!(bytes_of(1), small_size, "La libération n'a pas été retirée de son étiquette.");
+        assert_eq!(bytes_of(2), large_size);
+
+        allocator.dealloc(b, small);
+        allocator.dealloc(c, large);
+        assert_eq!(bytes_of(1), 0);
+        assert_eq!(bytes_of(2), 0);
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_vec_dans_l_allocateur() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+    }
+
+    let mut values = Vec::new_in(&allocator);
+    values.extend(0..16u32);
+    assert_eq!(values.iter().sum::<u32>(), 120);
+    assert!(heap.0.as_ptr_range().contains(&(values.as_ptr() as *const u8)));
+    assert!(allocator.stats().allocated_bytes >= 16 * 4);
+
+    drop(values);
+    assert_eq!(allocator.stats().allocated_bytes, 0);
+}
+
+#[test]
+fn test_contains() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    let local = 0u64;
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+        let ptr = allocator.alloc(layout);
+
+        assert!(allocator.contains(ptr), "Le pointeur alloué devrait appartenir au tas.");
+        assert!(!allocator.contains(&local as *const u64 as *const u8), "Une adresse de la pile n'appartient pas au tas.");
+
+        allocator.dealloc(ptr, layout);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_rejeu_d_une_charge_de_travail() {
+    use allocateurharjit::WorkloadStats;
+
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::with_strategy(Strategy::BestFit);
+    let ops = [
+        Op::Alloc { size: 128, align: 8 },
+        Op::Alloc { size: 128, align: 8 },
+        Op::Alloc { size: 128, align: 8 },
+        Op::Alloc { size: 128, align: 8 },
+        Op::Free { index: 1 },
+        Op::Alloc { size: 64, align: 8 },
+        Op::Alloc { size: 1024, align: 8 }, // Plus de place
+        Op::Alloc { size: 8, align: 3 },    // Layout invalide
+        Op::Free { index: 0 },
+        Op::Free { index: 9 }, // Allocation inexistante
+    ];
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, heap.0.len());
+    }
+
+    let result = allocator.run_workload(&ops);
+    assert_eq!(result, WorkloadStats { successes: 5, failures: 2, skipped: 0, peak_used: 512 });
+
+    // Les allocations restantes ont été libérées.
+    unsafe {
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+    assert_eq!(allocator.count_free_blocks(), 1);
+    assert_eq!(allocator.total_free(), 1024);
+}
+
+#[test]
+fn test_rejeu_au_dela_des_allocations_suivies() {
+    use allocateurharjit::MAX_WORKLOAD_ALLOCS;
+
+    static HEAP: StaticHeap<16384> = StaticHeap::new();
+    let allocator = FreeListAllocator::new();
+    let (start, size) = HEAP.as_region();
+    unsafe { allocator.init(start, size) };
+
+    // Les allocations au-delà des suivies ne sont pas tentées : tout est rendu à la fin.
+    let ops = [Op::Alloc { size: 16, align: 8 }; 300];
+    let result = allocator.run_workload(&ops);
+    assert_eq!(result.successes, MAX_WORKLOAD_ALLOCS);
+    assert_eq!(result.skipped, 300 - MAX_WORKLOAD_ALLOCS);
+    assert_eq!(allocator.stats().allocated_bytes, 0);
+
+    // Un layout invalide garde son indice : `Free { index: 1 }` libère bien la deuxième.
+    let alone = allocator.run_workload(&[Op::Alloc { size: 64, align: 8 }]);
+    let ops = [
+        Op::Alloc { size: 8, align: 3 },
+        Op::Alloc { size: 64, align: 8 },
+        Op::Free { index: 1 },
+        Op::Alloc { size: 64, align: 8 },
+    ];
+    let result = allocator.run_workload(&ops);
+    assert_eq!(result.failures, 1);
+    assert_eq!(result.peak_used, alone.peak_used);
+    assert_eq!(allocator.stats().allocated_bytes, 0);
+}
+
+#[test]
+fn test_alignement_minimal_impose() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new().with_min_align(64);
+    let layout = Layout::from_size_align(24, 8).unwrap();
+
+    unsafe {
+        // Début du tas volontairement décalé par rapport à 64 octets.
+        let start = (heap.0.as_mut_ptr() as usize).div_ceil(64) * 64 + 8;
+        allocator.init(start, 768);
+
+        let first = allocator.alloc(layout);
+        let second = allocator.alloc(layout);
+        assert_eq!(first as usize % 64, 0, "Le premier bloc n'est pas aligné sur 64 octets.");
+        assert_eq!(second as usize % 64, 0, "Le reste du découpage n'était pas aligné.");
+
+        allocator.dealloc(first, layout);
+        allocator.dealloc(second, layout);
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_preparation_d_un_pool() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, heap.0.len());
+        assert_eq!(allocator.prepare_pool(64, 8), 8);
+
+        // Huit blocs de 64 octets, plus le reste du tas.
+        let chunks = allocator.free_blocks().filter(|&(_, size)| size == 64).count();
+        assert_eq!(chunks, 8, "Le pool n'a pas le nombre de blocs attendu.");
+        assert_eq!(allocator.count_free_blocks(), 9);
+        assert_eq!(allocator.total_free(), 1024);
+        assert_eq!(allocator.validate(), Ok(()));
+
+        // Une allocation de la taille du pool prend le premier bloc, sans découpage.
+        let ptr = allocator.alloc(layout);
+        assert_eq!(ptr as usize, base);
+        assert_eq!(allocator.count_free_blocks(), 8);
+
+        // Plus de blocs que la mémoire ne le permet : seul le reste de 512 octets est découpé,
+        // les blocs de 64 octets sont trop petits.
+        assert_eq!(allocator.prepare_pool(128, 100), 4);
+        assert_eq!(allocator.count_free_blocks(), 11);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_nombre_de_blocs_examines() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        // Cinq blocs de 64 octets séparés, puis un bloc de 120 octets de la même classe.
+        for i in 0..5 {
+            allocator.insert_free_region(base + i * 128, 64);
+        }
+        allocator.insert_free_region(base + 640, 120);
+        assert_eq!(allocator.nodes_visited(), 0);
+
+        // Le premier bloc convient.
+        allocator.alloc(Layout::from_size_align(64, 8).unwrap());
+        let first = allocator.nodes_visited();
+        assert_eq!(first, 1);
+
+        // Les quatre blocs trop petits restants sont examinés avant le bon.
+        allocator.alloc(Layout::from_size_align(120, 8).unwrap());
+        let second = allocator.nodes_visited() - first;
+        assert!(second > first, "La recherche longue aurait dû examiner plus de blocs.");
+        assert_eq!(second, 5);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags")))]
+#[test]
+fn test_grand_alignement_sur_un_tas_non_aligne() {
+    #[repr(C, align(256))]
+    struct AlignedHeap([u8; 2048]);
+
+    let mut heap = AlignedHeap([0; 2048]);
+    let aligned = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 256).unwrap();
+
+    unsafe {
+        // Le tas commence 16 octets avant une frontière de 256 : trop peu pour un bloc libre,
+        // sauf avec un en-tête plus petit que 16 octets.
+        let start = aligned + 240;
+        let expected = if 16 >= <FreeListAllocator>::MIN_BLOCK_SIZE { aligned + 256 } else { aligned + 512 };
+        allocator.init(start, 1536);
+
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null(), "L'allocation très alignée a échoué.");
+        assert_eq!(ptr as usize % 256, 0, "Le pointeur n'est pas aligné sur 256 octets.");
+        assert_eq!(ptr as usize, expected, "Le début sauté aurait été trop petit pour être rendu.");
+
+        // Le début sauté et la fin du bloc (arrondi à 256 octets) retournent dans la liste libre.
+        let mut blocks = allocator.free_blocks().collect::<Vec<_>>();
+        blocks.sort();
+        assert_eq!(blocks, [(start, expected - start), (expected + 256, start + 1536 - expected - 256)]);
+
+        allocator.dealloc(ptr, layout);
+        assert_eq!(allocator.count_free_blocks(), 1);
+        assert_eq!(allocator.total_free(), 1536);
+    }
+}
+
+#[test]
+fn test_try_init_refuse_les_tas_degeneres() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        assert_eq!(allocator.try_init(base, 4), Err(InitError::TooSmall));
+        assert_eq!(allocator.try_init(base + 1, 512), Err(InitError::Misaligned));
+        assert_eq!(allocator.max_possible_alloc(), 0, "Un tas refusé a été confié à l'allocateur.");
+
+        assert_eq!(allocator.try_init(base, heap.0.len()), Ok(()));
+        assert_eq!(allocator.total_free(), 1024);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_profondeur_de_recherche_limitee() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(120, 8).unwrap();
+
+    unsafe {
+        // Cinq blocs trop petits de la même classe, puis un bloc qui convient.
+        for i in 0..5 {
+            allocator.insert_free_region(base + i * 128, 64);
+        }
+        allocator.insert_free_region(base + 640, 120);
+
+        allocator.set_max_search(3);
+        assert!(allocator.alloc(layout).is_null(), "La recherche aurait dû s'arrêter avant le bon bloc.");
+        assert_eq!(allocator.nodes_visited(), 3);
+
+        // Sans limite, le dernier bloc est trouvé.
+        allocator.set_max_search(0);
+        assert_eq!(allocator.alloc(layout) as usize, base + 640);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags")))]
+#[test]
+fn test_petite_fin_laissee_a_l_allocation() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let small = Layout::from_size_align(64, 8).unwrap();
+    let hole = Layout::from_size_align(72, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, heap.0.len());
+
+        // Un trou de 72 octets, suivi d'un bloc occupé.
+        let a = allocator.alloc(hole);
+        let b = allocator.alloc(small);
+        allocator.dealloc(a, hole);
+
+        // Y découper 64 octets laisserait une fin de 8 octets, plus petite qu'un en-tête.
+        let c = allocator.alloc(small);
+        assert_eq!(c as usize, base);
+        assert_eq!(allocator.count_free_blocks(), 1, "Un bloc trop petit a été créé.");
+        assert_eq!(allocator.validate(), Ok(()));
+
+        // La fin revient avec l'allocation à sa libération : le tas redevient un seul bloc.
+        allocator.dealloc(b, small);
+        allocator.dealloc(c, small);
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(base, 1024)]);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_taille_utilisable() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let odd = Layout::from_size_align(20, 8).unwrap();
+    let small = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 512);
+        allocator.add_region(base + 512, 72);
+
+        // 20 octets sont arrondis à la taille minimale d'un bloc, puis à l'alignement.
+        let a = allocator.alloc(odd);
+        let usable = allocator.usable_size(a, odd);
+        assert!(usable >= 20);
+        assert_eq!(usable, 20usize.max(<FreeListAllocator>::MIN_BLOCK_SIZE).next_multiple_of(8));
+
+        // Remplir la seconde région laisse une fin de 8 octets dans l'allocation.
+        allocator.dealloc(a, odd);
+        let big = allocator.alloc(Layout::from_size_align(512, 8).unwrap());
+        let b = allocator.alloc(small);
+        assert_eq!(b as usize, base + 512);
+        assert_eq!(allocator.usable_size(b, small), 72);
+
+        // Toute la place utilisable peut servir sans réallouer.
+        core::ptr::write_bytes(b, 0xCD, 72);
+        allocator.dealloc(b, small);
+        allocator.dealloc(big, Layout::from_size_align(512, 8).unwrap());
+        assert_eq!(allocator.total_free(), 584);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_tas_statique_branche_sur_l_allocateur() {
+    // Le `Heap` local des tests masque celui de la bibliothèque.
+    static HEAP: allocateurharjit::Heap<2048> = allocateurharjit::Heap::new();
+    let allocator = FreeListAllocator::new();
+    HEAP.init_allocator(&allocator);
+
+    let layout = Layout::from_size_align(1024, 8).unwrap();
+    unsafe {
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null(), "Le tas statique n'a pas été confié à l'allocateur.");
+        assert!(allocator.contains(ptr));
+        assert!(allocator.total_free() > 2048 - 1024 - 8);
+        allocator.dealloc(ptr, layout);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_octets_manquants_a_l_echec() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        allocator.init(base, 1024);
+        assert_eq!(allocator.last_alloc_shortfall(), 0);
+
+        // Le plus grand bloc libre fait 1024 - 256 = 768 octets.
+        let small = Layout::from_size_align(256, 8).unwrap();
+        let a = allocator.alloc(small);
+        assert!(!a.is_null());
+
+        let too_big = Layout::from_size_align(768 + 40, 8).unwrap();
+        assert!(allocator.alloc(too_big).is_null());
+        assert_eq!(allocator.last_alloc_shortfall(), 40);
+
+        // Une allocation réussie ne remet pas la mesure à zéro.
+        allocator.dealloc(a, small);
+        let b = allocator.alloc(small);
+        assert!(!b.is_null());
+        assert_eq!(allocator.last_alloc_shortfall(), 40);
+        allocator.dealloc(b, small);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_allocation_depuis_le_haut_du_tas() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new().with_from_top(true);
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        // La première allocation occupe la fin du tas, les suivantes descendent.
+        let mut ptrs = [core::ptr::null_mut(); 4];
+        for ptr in ptrs.iter_mut() {
+            *ptr = allocator.alloc(layout);
+            assert!(!ptr.is_null());
+        }
+        assert_eq!(ptrs[0] as usize, base + 1024 - 64);
+        for pair in ptrs.windows(2) {
+            assert_eq!(pair[1] as usize, pair[0] as usize - 64, "Les allocations devraient descendre.");
+        }
+
+        // Le début du tas reste un seul bloc libre.
+        assert_eq!(allocator.largest_free_block(), 1024 - 4 * 64);
+
+        for ptr in ptrs {
+            allocator.dealloc(ptr, layout);
+        }
+        assert_eq!(allocator.largest_free_block(), 1024);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_parcours_des_blocs_alloues() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+        assert_eq!(allocator.allocated_blocks().count(), 0);
+
+        // Le bloc libéré entre `a` et `b` les sépare en deux intervalles.
+        let a = allocator.alloc(layout);
+        let between = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        allocator.dealloc(between, layout);
+
+        let mut blocks = [(0, 0); 2];
+        assert_eq!(allocator.allocated_blocks().count(), 2);
+        for (slot, block) in blocks.iter_mut().zip(allocator.allocated_blocks()) {
+            *slot = block;
+        }
+        assert_eq!(blocks, [(a as usize, 64), (b as usize, 64)]);
+
+        allocator.dealloc(a, layout);
+        allocator.dealloc(b, layout);
+        assert_eq!(allocator.allocated_blocks().count(), 0);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_retour_a_une_marque() {
+    use allocateurharjit::Mark;
+
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(32, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+        assert!(allocator.begin_bump(512));
+        assert!(!allocator.begin_bump(64), "Une seule arène peut être active.");
+
+        let kept = allocator.alloc(layout);
+        let mark: Mark = allocator.mark();
+
+        // Les allocations suivent la marque et les libérations sont ignorées.
+        let first = allocator.alloc(layout);
+        let second = allocator.alloc(layout);
+        assert_eq!(first as usize, kept as usize + 32);
+        assert_eq!(second as usize, first as usize + 32);
+        allocator.dealloc(first, layout);
+
+        // Le retour à la marque libère tout ce qui a été alloué depuis.
+        allocator.reset_to(mark);
+        assert_eq!(allocator.mark(), mark);
+        assert_eq!(allocator.alloc(layout), first);
+
+        // L'arène retourne entière aux listes libres.
+        allocator.end_bump();
+        assert_eq!(allocator.total_free(), 1024);
+        assert_eq!(allocator.count_free_blocks(), 1);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags")))]
+#[test]
+fn test_fusion_puis_nouvel_essai_a_l_echec() {
+    let layout = Layout::from_size_align(384, 8).unwrap();
+
+    for retry in [false, true] {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new().with_coalesce_retry(retry);
+
+        unsafe {
+            // Deux blocs adjacents de 256 octets, chacun trop petit pour la requête.
+            allocator.init(base, 512);
+            assert_eq!(allocator.prepare_pool(256, 1), 1);
+            assert_eq!(allocator.count_free_blocks(), 2);
+
+            let ptr = allocator.alloc(layout);
+            if retry {
+                assert_eq!(ptr as usize, base, "La fusion aurait dû permettre l'allocation.");
+                allocator.dealloc(ptr, layout);
+                assert_eq!(allocator.count_free_blocks(), 1);
+            } else {
+                assert!(ptr.is_null(), "Sans fusion, l'allocation aurait dû échouer.");
+                assert_eq!(allocator.count_free_blocks(), 2);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_histogramme_des_tailles() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        allocator.init(base, 1024);
+        assert_eq!(allocator.size_histogram(), [0; 32]);
+
+        for size in [8, 40, 300, 40] {
+            let layout = Layout::from_size_align(size, 8).unwrap();
+            let ptr = allocator.alloc(layout);
+            assert!(!ptr.is_null());
+            allocator.dealloc(ptr, layout);
+        }
+
+        // 8 octets dans la case 3, 40 dans la case 5 et 300 dans la case 8.
+        let mut expected = [0; 32];
+        expected[3] = 1;
+        expected[5] = 2;
+        expected[8] = 1;
+        assert_eq!(allocator.size_histogram(), expected);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags")))]
+#[test]
+fn test_init_aligne_un_tas_mal_aligne() {
+    use allocateurharjit::HEAP_ALIGN;
+
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(512, 8).unwrap();
+
+    // Seule la sous-région dont le début et la fin sont alignés sur `HEAP_ALIGN` est utilisable.
+    let start = (base + 3).next_multiple_of(HEAP_ALIGN);
+    let size = ((base + 3 + 1000) & !(HEAP_ALIGN - 1)) - start;
+
+    unsafe {
+        allocator.init(base + 3, 1000);
+        assert_eq!(allocator.total_free(), size);
+        assert_eq!(allocator.free_blocks().next(), Some((start, size)));
+
+        // Un début aligné sur moins de 8 octets laisse devant l'allocation un bloc libre.
+        let expected = if start.is_multiple_of(8) { start } else { (start + <FreeListAllocator>::MIN_BLOCK_SIZE).next_multiple_of(8) };
+        let ptr = allocator.alloc(layout);
+        assert_eq!(ptr as usize, expected);
+        assert!(allocator.contains(ptr));
+        allocator.dealloc(ptr, layout);
+        assert_eq!(allocator.total_free(), size);
+    }
+}
+
+#[cfg(feature = "header")]
+#[test]
+fn test_seuil_de_decoupage() {
+    let mut counts = [0; 2];
+
+    for (count, threshold) in counts.iter_mut().zip([0, 128]) {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+        allocator.set_split_threshold(threshold);
+        let large = Layout::from_size_align(200, 8).unwrap();
+        let small = Layout::from_size_align(40, 8).unwrap();
+
+        unsafe {
+            allocator.init(base, 1024);
+
+            // Le bloc libéré au milieu (208 octets) laisse un reste de 160 octets à l'allocation
+            // suivante (48 octets) : il dépasse 48 + 0 mais pas 48 + 128.
+            let a = allocator.alloc(large);
+            let b = allocator.alloc(large);
+            let c = allocator.alloc(large);
+            allocator.dealloc(b, large);
+            let d = allocator.alloc(small);
+            *count = allocator.count_free_blocks();
+
+            // Le reste laissé à l'allocation revient avec elle.
+            for (ptr, layout) in [(a, large), (c, large), (d, small)] {
+                allocator.dealloc(ptr, layout);
+            }
+            assert_eq!(allocator.total_free(), 1024);
+            assert_eq!(allocator.count_free_blocks(), 1);
+        }
+    }
+
+    assert_eq!(counts, [2, 1], "Le seuil élevé aurait dû éviter un petit bloc libre.");
+}
+
+#[test]
+fn test_placement_aleatoire() {
+    let layout = Layout::from_size_align(64, 8).unwrap();
+    let mut offsets = [[0; 3]; 2];
+
+    for run in offsets.iter_mut() {
+        let mut heap = Heap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::new();
+        allocator.set_placement_seed(0x2545_F491);
+
+        unsafe {
+            allocator.init(base, 1024);
+
+            let mut ptrs = [core::ptr::null_mut(); 3];
+            for (ptr, offset) in ptrs.iter_mut().zip(run.iter_mut()) {
+                *ptr = allocator.alloc(layout);
+                assert!(!ptr.is_null());
+                assert!((*ptr as usize).is_multiple_of(8));
+                *offset = *ptr as usize - base;
+            }
+            assert_eq!(allocator.validate(), Ok(()));
+
+            for ptr in ptrs {
+                allocator.dealloc(ptr, layout);
+            }
+            assert_eq!(allocator.validate(), Ok(()));
+            assert_eq!(allocator.total_free(), 1024);
+            assert_eq!(allocator.count_free_blocks(), 1);
+        }
+    }
+
+    // La même graine donne les mêmes positions, ni au début du tas ni à la suite. Avec un
+    // en-tête réduit, le tirage peut laisser au début du tas un bloc juste assez grand.
+    assert_eq!(offsets[0], offsets[1]);
+    #[cfg(not(feature = "small_header"))]
+    assert!(offsets[0].iter().all(|&offset| offset != 0), "Positions tirées : {:?}", offsets[0]);
+    assert!(offsets[0].windows(2).any(|pair| pair[1] != pair[0] + 64), "Positions tirées : {:?}", offsets[0]);
+}
+
+#[test]
+fn test_octets_comptabilises() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let small = Layout::from_size_align(64, 8).unwrap();
+    let large = Layout::from_size_align(200, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+        allocator.set_reserve(128);
+
+        let a = allocator.alloc(small);
+        let b = allocator.alloc(large);
+        let c = allocator.alloc(small);
+        allocator.dealloc(b, large);
+        assert_eq!(allocator.accounted_bytes(), allocator.heap_size());
+
+        allocator.dealloc(a, small);
+        allocator.dealloc(c, small);
+        assert_eq!(allocator.accounted_bytes(), 1024);
+
+        // Un bloc libre dont la taille est corrompue fausse le compte.
+        let (block, _) = allocator.free_blocks().next().unwrap();
+        *(block as *mut usize) += 64;
+        assert_eq!(allocator.accounted_bytes(), 1024 + 64);
+    }
+}
+
+#[test]
+fn test_reallocation_avec_un_alignement_plus_fort() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let old = Layout::from_size_align(32, 8).unwrap();
+    let new = Layout::from_size_align(100, 64).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        // Le bloc de tête, de 24 octets quel que soit l'en-tête, décale l'allocation hors
+        // d'une adresse multiple de 64.
+        let filler = allocator.alloc(Layout::from_size_align(24, 8).unwrap());
+        let ptr = allocator.alloc(old);
+        assert!(!(ptr as usize).is_multiple_of(64));
+        for i in 0..32 {
+            *ptr.add(i) = i as u8;
+        }
+
+        let moved = allocator.realloc_aligned(ptr, old, new.size(), new.align());
+        assert!(!moved.is_null());
+        assert!((moved as usize).is_multiple_of(64), "Le bloc n'est pas aligné sur 64 octets.");
+        for i in 0..32 {
+            assert_eq!(*moved.add(i), i as u8, "Le contenu n'a pas été conservé.");
+        }
+
+        allocator.dealloc(moved, new);
+        allocator.dealloc(filler, Layout::from_size_align(24, 8).unwrap());
+        assert_eq!(allocator.total_free(), 1024);
+        assert_eq!(allocator.count_free_blocks(), 1);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_seuil_de_memoire_basse() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static LAST_FREE: AtomicUsize = AtomicUsize::new(0);
+
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(256, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+        allocator.set_low_watermark(600, |free| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            LAST_FREE.store(free, Ordering::SeqCst);
+        });
+
+        // 768 octets libres : encore au-dessus du seuil.
+        let a = allocator.alloc(layout);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+
+        // Le franchissement vers le bas appelle la fonction, une seule fois.
+        let b = allocator.alloc(layout);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        assert_eq!(LAST_FREE.load(Ordering::SeqCst), 512);
+        let c = allocator.alloc(layout);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        // Remonter au-dessus du seuil ne l'appelle pas, mais le réarme.
+        allocator.dealloc(c, layout);
+        allocator.dealloc(b, layout);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        let b = allocator.alloc(layout);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+        allocator.dealloc(b, layout);
+        allocator.dealloc(a, layout);
+    }
+}
+
+#[cfg(feature = "small_header")]
+#[test]
+fn test_en_tete_reduit() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(1, 1).unwrap();
+
+    // Taille et chaînage sur 32 bits : le plus petit bloc tient en douze octets.
+    let min_block = <FreeListAllocator>::MIN_BLOCK_SIZE;
+    assert_eq!(min_block, 12);
+    assert!(min_block < 2 * core::mem::size_of::<usize>());
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        let mut ptrs = [core::ptr::null_mut(); 4];
+        for (i, ptr) in ptrs.iter_mut().enumerate() {
+            *ptr = allocator.alloc(layout);
+            assert_eq!(*ptr as usize, base + i * min_block);
+            **ptr = i as u8;
+        }
+
+        // Les blocs libérés dans le désordre se rechaînent et refusionnent.
+        for i in [2, 0, 3, 1] {
+            assert_eq!(*ptrs[i], i as u8);
+            allocator.dealloc(ptrs[i], layout);
+        }
+        assert_eq!(allocator.total_free(), 1024);
+        assert_eq!(allocator.count_free_blocks(), 1);
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_liberation_groupee_dans_le_desordre() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        // Seize blocs adjacents remplissent tout le tas.
+        let mut ptrs = [(core::ptr::null_mut(), layout); 16];
+        for (i, (ptr, _)) in ptrs.iter_mut().enumerate() {
+            *ptr = allocator.alloc(layout);
+            assert_eq!(*ptr as usize, base + i * 64);
+        }
+        assert_eq!(allocator.count_free_blocks(), 0);
+
+        // Ordre mélangé par une permutation fixe de 0..16.
+        let mut shuffled = ptrs;
+        for (i, slot) in shuffled.iter_mut().enumerate() {
+            *slot = ptrs[(i * 7 + 3) % 16];
+        }
+
+        assert_eq!(allocator.free_all(&shuffled), 1);
+        assert_eq!(allocator.free_blocks().next(), Some((base, 1024)));
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_region_libre_rendue() {
+    static RELEASED: Mutex<Option<(usize, usize)>> = Mutex::new(None);
+
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(128, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 256);
+        allocator.add_region(base + 512, 512);
+        allocator.set_release_callback(256, |addr, size| *RELEASED.lock().unwrap() = Some((addr, size)));
+
+        // La première région est trop petite pour être rendue.
+        let a = allocator.alloc(layout);
+        assert_eq!(a as usize, base);
+        allocator.dealloc(a, layout);
+        assert_eq!(*RELEASED.lock().unwrap(), None);
+
+        // Une région encore partiellement allouée reste en place.
+        let b = allocator.alloc(Layout::from_size_align(384, 8).unwrap());
+        let c = allocator.alloc(layout);
+        assert_eq!(b as usize, base + 512);
+        allocator.dealloc(b, Layout::from_size_align(384, 8).unwrap());
+        assert_eq!(*RELEASED.lock().unwrap(), None);
+
+        // La libération du dernier bloc rend toute la seconde région.
+        allocator.dealloc(c, layout);
+        assert_eq!(*RELEASED.lock().unwrap(), Some((base + 512, 512)));
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(base, 256)]);
+        assert_eq!(allocator.heap_size(), 256);
+        assert!(!allocator.contains((base + 512) as *const u8));
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn test_tas_aligne_enregistre_en_entier() {
+    use allocateurharjit::{AlignedHeap, HEAP_ALIGN};
+
+    const _: () = assert!(core::mem::align_of::<AlignedHeap<1024>>() >= HEAP_ALIGN);
+
+    let mut heap = AlignedHeap::<1024>::new();
+    let allocator = FreeListAllocator::new();
+
+    unsafe {
+        let (heap_start, heap_size) = heap.as_region();
+        assert!(heap_start.is_multiple_of(HEAP_ALIGN));
+        allocator.init(heap_start, heap_size);
+
+        assert_eq!(allocator.heap_size(), 1024);
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(heap_start, 1024)]);
+    }
+}
+
+#[test]
+fn test_remplissage_des_allocations() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(200, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+        allocator.set_alloc_fill(Some(0xCC));
+
+        let ptr = allocator.alloc(layout);
+        let buffer = core::slice::from_raw_parts(ptr, layout.size());
+        assert!(buffer.iter().all(|&byte| byte == 0xCC), "La mémoire rendue n'est pas remplie de 0xCC.");
+
+        // `alloc_zeroed` n'est pas concerné par le remplissage.
+        let zeroed = allocator.alloc_zeroed(layout);
+        assert!(core::slice::from_raw_parts(zeroed, layout.size()).iter().all(|&byte| byte == 0));
+    }
+}
+
+#[test]
+fn test_cases_rapides_pour_les_petits_blocs() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new().with_small_bins(true);
+    let layout = Layout::from_size_align(32, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        let _c = allocator.alloc(layout);
+        let size = b as usize - a as usize;
+
+        // Les petits blocs libérés restent dans leur case, sans fusion avec leur voisin : ils
+        // suivent les listes triées, dernier libéré en tête.
+        allocator.dealloc(a, layout);
+        allocator.dealloc(b, layout);
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(base + 3 * size, 1024 - 3 * size), (base + size, size), (base, size)]);
+        assert_eq!(allocator.total_free(), 1024 - size);
+        assert_eq!(allocator.allocated_blocks().collect::<Vec<_>>(), [(base + 2 * size, size)]);
+        assert_eq!(allocator.stats().free_bytes, 1024 - size);
+
+        // La case sert la même taille en premier, dernier libéré en tête.
+        assert_eq!(allocator.alloc(layout), b);
+        assert_eq!(allocator.alloc(layout), a);
+        assert_eq!(allocator.stats().free_bytes, 1024 - 3 * size);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_grands_blocs_dans_les_listes_triees() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new().with_small_bins(true);
+    let large = Layout::from_size_align(256, 8).unwrap();
+    let small = Layout::from_size_align(32, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        let a = allocator.alloc(large);
+        let b = allocator.alloc(large);
+        let _c = allocator.alloc(small);
+
+        // Au-delà de `SMALL_BIN_MAX`, les blocs libérés fusionnent dans les listes triées.
+        allocator.dealloc(a, large);
+        allocator.dealloc(b, large);
+        let mut blocks: Vec<_> = allocator.free_blocks().collect();
+        blocks.sort();
+        assert_eq!(blocks, [(base, 512), (base + 544, 1024 - 544)]);
+        assert_eq!(allocator.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn test_cases_rapides_videes_pour_fusionner() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new().with_small_bins(true);
+    let small = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        let count = allocator.remaining_capacity(small);
+        let ptrs: Vec<_> = (0..count).map(|_| allocator.alloc(small)).collect();
+        assert!(ptrs.iter().all(|ptr| !ptr.is_null()), "L'allocation a échoué.");
+        let tail = allocator.count_free_blocks();
+        for &ptr in &ptrs {
+            allocator.dealloc(ptr, small);
+        }
+
+        // Chaque bloc libéré attend dans sa case et compte parmi les blocs libres.
+        assert_eq!(allocator.count_free_blocks(), tail + count);
+        assert_eq!(allocator.accounted_bytes(), 1024);
+        assert!(!allocator.is_exhausted());
+        assert!(allocator.can_allocate_all(&[small, small]));
+
+        // Aucune case ne suffit : elles sont vidées et leurs blocs refondus en un seul, dont
+        // l'allocation ne laisse qu'un reste.
+        let large = Layout::from_size_align(512, 8).unwrap();
+        let ptr = allocator.alloc(large);
+        assert!(!ptr.is_null(), "Les cases n'ont pas été vidées.");
+        assert_eq!(allocator.count_free_blocks(), 1);
+        allocator.dealloc(ptr, large);
+
+        // `coalesce_all` vide aussi les cases.
+        let a = allocator.alloc(small);
+        let b = allocator.alloc(small);
+        allocator.dealloc(a, small);
+        allocator.dealloc(b, small);
+        assert_eq!(allocator.accounted_bytes(), 1024);
+        allocator.coalesce_all();
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(base, 1024)]);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_simulation_d_une_rafale_d_allocations() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = |size| Layout::from_size_align(size, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+
+        // La suite remplit exactement le tas ; un octet de plus ne tient plus.
+        assert!(allocator.can_allocate_all(&[layout(512), layout(256), layout(256)]));
+        assert!(!allocator.can_allocate_all(&[layout(512), layout(256), layout(257)]));
+
+        // La simulation ne touche pas aux listes libres.
+        assert_eq!(allocator.stats().allocated_bytes, 0);
+        assert_eq!(allocator.count_free_blocks(), 1);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_capacite_restante() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+
+        // 1024 octets / 64 octets par allocation, sans en-tête.
+        assert_eq!(allocator.remaining_capacity(layout), 16);
+
+        let ptrs: Vec<_> = (0..5).map(|_| allocator.alloc(layout)).collect();
+        assert_eq!(allocator.remaining_capacity(layout), 11);
+
+        // Une allocation de 100 octets coûte 104 octets ajustés : 6 tiennent dans les 704 restants.
+        assert_eq!(allocator.remaining_capacity(Layout::from_size_align(100, 8).unwrap()), 6);
+        assert_eq!(allocator.remaining_capacity(Layout::from_size_align(0, 8).unwrap()), usize::MAX);
+
+        for ptr in ptrs {
+            allocator.dealloc(ptr, layout);
+        }
+        assert_eq!(allocator.remaining_capacity(layout), 16);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_tete_de_liste_pour_un_debogueur() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(1024, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        let head = allocator.free_list_head();
+        assert!(!head.is_null());
+        assert_eq!(head as usize, base);
+        assert_eq!((*head).size(), 1024);
+        assert!((*head).next().is_null());
+
+        // Plus aucun bloc libre une fois le tas épuisé.
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null());
+        assert!(allocator.free_list_head().is_null());
+        assert!(allocator.free_list_heads().iter().all(|head| head.is_null()));
+
+        allocator.dealloc(ptr, layout);
+        assert_eq!(allocator.free_list_head() as usize, base);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags")))]
+#[test]
+fn test_arrondi_aux_lignes_de_cache() {
+    #[repr(C, align(64))]
+    struct LineHeap([u8; 1024]);
+
+    let mut heap = LineHeap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new().with_cache_line_rounding(true).with_cache_line_size(64);
+    let layout = Layout::from_size_align(10, 1).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        // Une requête de 10 octets occupe toute une ligne ; la suivante commence à la ligne d'après.
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        assert_eq!(a as usize, base);
+        assert_eq!(b as usize, base + 64);
+        assert_eq!(allocator.stats().allocated_bytes, 128);
+
+        allocator.dealloc(a, layout);
+        allocator.dealloc(b, layout);
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(base, 1024)]);
+    }
+}
+
+#[test]
+#[cfg(feature = "fault_injection")]
+fn test_echecs_provoques() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+        allocator.fail_next_n(2);
+
+        // Une allocation de taille nulle réussit sans consommer d'échec.
+        assert!(!allocator.alloc(Layout::from_size_align(0, 8).unwrap()).is_null());
+
+        // Les deux premières allocations échouent malgré la mémoire disponible.
+        assert!(allocator.alloc(layout).is_null());
+        assert_eq!(allocator.try_alloc(layout), Err(AllocError::OutOfMemory));
+        assert!(!allocator.alloc(layout).is_null(), "La troisième allocation aurait dû réussir.");
+    }
+}
+
+#[test]
+fn test_allocations_en_suspens() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+
+        let ptrs = [allocator.alloc(layout), allocator.alloc(layout), allocator.alloc(layout)];
+        allocator.dealloc(ptrs[0], layout);
+        allocator.dealloc(ptrs[2], layout);
+
+        assert_eq!(allocator.stats().dealloc_count, 2);
+        assert_eq!(allocator.outstanding_allocations(), 1);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_compactage_des_allocations() {
+    #[repr(C, align(64))]
+    struct LineHeap([u8; 1024]);
+
+    let mut heap = LineHeap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        let ptrs = [allocator.alloc(layout), allocator.alloc(layout), allocator.alloc(layout), allocator.alloc(layout)];
+        for (i, &ptr) in ptrs.iter().enumerate() {
+            ptr.write_bytes(i as u8 + 1, layout.size());
+        }
+        allocator.dealloc(ptrs[0], layout);
+        allocator.dealloc(ptrs[2], layout);
+        let mut live = [ptrs[1], ptrs[3]];
+
+        // Chaque plage déplacée entraîne les pointeurs qu'elle contient.
+        allocator.compact(|old, new, len| {
+            for ptr in live.iter_mut() {
+                if (old as usize..old as usize + len).contains(&(*ptr as usize)) {
+                    *ptr = new.add(*ptr as usize - old as usize);
+                }
+            }
+        });
+
+        assert_eq!(live, [base as *mut u8, (base + 64) as *mut u8]);
+        assert!(core::slice::from_raw_parts(live[0], 64).iter().all(|&byte| byte == 2));
+        assert!(core::slice::from_raw_parts(live[1], 64).iter().all(|&byte| byte == 4));
+
+        // Toute la mémoire libre ne forme plus qu'un bloc.
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(base + 128, 1024 - 128)]);
+        assert_eq!(allocator.stats().free_bytes, 1024 - 128);
+        assert_eq!(allocator.validate(), Ok(()));
+
+        for ptr in live {
+            allocator.dealloc(ptr, layout);
+        }
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), [(base, 1024)]);
+    }
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_meilleur_ajustement_selon_l_alignement() {
+    #[repr(C, align(64))]
+    struct LineHeap([u8; 1024]);
+
+    let layout = Layout::from_size_align(64, 64).unwrap();
+
+    let place = |strategy, second_size| {
+        let mut heap = LineHeap([0; 1024]);
+        let base = heap.0.as_mut_ptr() as usize;
+        let allocator = FreeListAllocator::with_strategy(strategy);
+
+        unsafe {
+            allocator.insert_free_region(base + 8, 136);
+            allocator.insert_free_region(base + 512, second_size);
+            allocator.alloc(layout) as usize - base
+        }
+    };
+
+    // Deux blocs de même taille, seul le second commence sur une adresse alignée : best-fit
+    // garde le premier trouvé, la variante alignée celui qui n'a pas de début sauté.
+    assert_eq!(place(Strategy::BestFit, 136), 64);
+    assert_eq!(place(Strategy::BestFitAligned, 136), 512, "Le bloc sans début sauté aurait dû être choisi.");
+
+    // Le gaspillage total ne dépend que de la taille : un bloc aligné plus grand perd.
+    assert_eq!(place(Strategy::BestFit, 144), 64);
+    assert_eq!(place(Strategy::BestFitAligned, 144), 64);
+}
+
+#[cfg(not(any(feature = "header", feature = "debug_tags", feature = "canaries")))]
+#[test]
+fn test_zone_rouge_en_haut_du_tas() {
+    let mut heap = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new().with_redzone(64);
+
+    unsafe {
+        allocator.init(base, 1024);
+
+        // Les 64 derniers octets ne sont jamais distribués.
+        assert_eq!(allocator.heap_size(), 1024 - 64);
+        assert!(!allocator.contains((base + 1024 - 64) as *const u8));
+        assert_eq!(allocator.check_redzone(), Ok(()));
+
+        let layout = Layout::from_size_align(1024 - 64, 8).unwrap();
+        let ptr = allocator.alloc(layout);
+        assert!(!ptr.is_null());
+        ptr.write_bytes(0xAA, layout.size());
+        assert_eq!(allocator.check_redzone(), Ok(()));
+
+        // Un débordement de la dernière allocation atteint la zone rouge.
+        ptr.add(layout.size() + 3).write(0xAA);
+        assert_eq!(allocator.check_redzone(), Err(base + 1024 - 64 + 3));
+    }
+}
+
+#[test]
+fn test_zone_rouge_unique_sur_plusieurs_tas() {
+    let mut heap = Heap([0; 1024]);
+    let mut other = Heap([0; 1024]);
+    let base = heap.0.as_mut_ptr() as usize;
+    let allocator = FreeListAllocator::new().with_redzone(64);
+
+    unsafe {
+        // Le second tas est confié entier : la zone rouge reste en haut du premier.
+        allocator.init(base, 1024);
+        allocator.init(other.0.as_mut_ptr() as usize, 1024);
+        assert_eq!(allocator.heap_size(), 2048 - 64);
+        ((base + 1024 - 64) as *mut u8).write(0);
+        assert_eq!(allocator.check_redzone(), Err(base + 1024 - 64));
+
+        // `reset` replace la zone rouge en haut du nouveau tas.
+        allocator.reset(other.0.as_mut_ptr() as usize, 1024);
+        assert_eq!(allocator.heap_size(), 1024 - 64);
+        assert_eq!(allocator.check_redzone(), Ok(()));
+    }
+}
+
+#[test]
+fn test_instantane_et_restauration() {
+    let mut heap = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+
+        let ptrs: Vec<_> = (0..6).map(|_| allocator.alloc(layout)).collect();
+        allocator.dealloc(ptrs[1], layout);
+        allocator.dealloc(ptrs[3], layout);
+
+        // Un tampon trop petit n'est pas écrit : la taille nécessaire est retournée.
+        let mut buf = [0u8; 512];
+        let needed = allocator.snapshot(&mut []);
+        assert_eq!(allocator.snapshot(&mut buf), needed);
+
+        let blocks: Vec<_> = allocator.free_blocks().collect();
+        let stats = allocator.stats();
+
+        // Les allocations suivantes écrasent les en-têtes des blocs libres.
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(Layout::from_size_align(512, 8).unwrap());
+        a.write_bytes(0xAA, 64);
+        b.write_bytes(0xBB, 512);
+        allocator.dealloc(ptrs[0], layout);
+        assert_ne!(allocator.free_blocks().collect::<Vec<_>>(), blocks);
+
+        assert_eq!(allocator.restore(&buf[..needed]), Ok(()));
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), blocks);
+        assert_eq!(allocator.stats(), stats);
+        assert_eq!(allocator.validate(), Ok(()));
+
+        // L'état rétabli reste utilisable.
+        assert_eq!(allocator.alloc(layout), ptrs[1]);
+    }
+}
+
+#[test]
+fn test_restauration_refuse_un_instantane_invalide() {
+    use allocateurharjit::ValidationError;
+
+    const WORD: usize = core::mem::size_of::<usize>();
+
+    let mut heap = Heap([0; 1024]);
+    let mut other = Heap([0; 1024]);
+    let allocator = FreeListAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(heap.0.as_mut_ptr() as usize, 1024);
+        let a = allocator.alloc(layout);
+        let _b = allocator.alloc(layout);
+        allocator.dealloc(a, layout);
+
+        let mut buf = [0u8; 512];
+        let needed = allocator.snapshot(&mut buf);
+        let blocks: Vec<_> = allocator.free_blocks().collect();
+
+        // Premier bloc après l'en-tête de 11 mots et l'unique région.
+        let block = 13 * WORD;
+        let set = |buf: &mut [u8], at: usize, word: usize| buf[at..at + WORD].copy_from_slice(&word.to_ne_bytes());
+
+        assert_eq!(allocator.restore(&buf[..needed - 1]), Err(RestoreError::Truncated));
+        assert_eq!(allocator.restore(&buf[..4 * WORD]), Err(RestoreError::Truncated));
+
+        let mut bad = buf;
+        set(&mut bad, 2 * WORD + block, usize::MAX);
+        assert_eq!(allocator.restore(&bad[..needed]), Err(RestoreError::InvalidList));
+
+        let mut bad = buf;
+        set(&mut bad, WORD + block, 4096);
+        assert_eq!(allocator.restore(&bad[..needed]), Err(RestoreError::InvalidBlock));
+
+        let mut bad = buf;
+        set(&mut bad, block, heap.0.as_ptr() as usize + 3);
+        assert_eq!(allocator.restore(&bad[..needed]), Err(RestoreError::InvalidBlock));
+
+        // Les blocs doivent aussi respecter les invariants de `validate` entre eux.
+        let first = heap.0.as_ptr() as usize;
+
+        let mut bad = buf;
+        set(&mut bad, WORD + block, 128);
+        assert_eq!(allocator.restore(&bad[..needed]), Err(RestoreError::Inconsistent(ValidationError::WrongSizeClass)));
+
+        let mut bad = buf;
+        set(&mut bad, block + 4 * WORD, first + 32);
+        assert_eq!(allocator.restore(&bad[..needed]), Err(RestoreError::Inconsistent(ValidationError::Overlap)));
+
+        let mut bad = buf;
+        set(&mut bad, 8 * WORD, first);
+        set(&mut bad, 9 * WORD, 64);
+        assert_eq!(allocator.restore(&bad[..needed]), Err(RestoreError::Inconsistent(ValidationError::Overlap)));
+
+        // Un refus ne modifie rien.
+        assert_eq!(allocator.free_blocks().collect::<Vec<_>>(), blocks);
+        assert_eq!(allocator.validate(), Ok(()));
+
+        // Une région ajoutée depuis l'instantané le rend caduc.
+        allocator.add_region(other.0.as_mut_ptr() as usize, 1024);
+        assert_eq!(allocator.restore(&buf[..needed]), Err(RestoreError::HeapChanged));
+    }
+}
@@ -0,0 +1,68 @@
+use allocateurharjit::{BuddyAllocator, StaticHeap};
+use core::alloc::{GlobalAlloc, Layout};
+
+#[test]
+fn test_decoupage_a_l_allocation() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let allocator = BuddyAllocator::new();
+
+    unsafe {
+        allocator.init(start, size);
+        assert_eq!(allocator.free_blocks_of_size(1024), 1);
+
+        // Le bloc de 1024 octets est coupé jusqu'à obtenir 64 octets.
+        let ptr = allocator.alloc(Layout::from_size_align(64, 8).unwrap());
+        assert_eq!(ptr as usize, start);
+        for size in [512, 256, 128, 64] {
+            assert_eq!(allocator.free_blocks_of_size(size), 1, "Il manque le compagnon de {} octets.", size);
+        }
+        assert_eq!(allocator.free_blocks_of_size(1024), 0);
+    }
+}
+
+#[test]
+fn test_fusion_des_compagnons() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let allocator = BuddyAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(start, size);
+
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        assert_eq!(b as usize, a as usize + 64, "Les deux blocs devraient être compagnons.");
+
+        // Tant que `b` est alloué, `a` ne peut pas fusionner.
+        allocator.dealloc(a, layout);
+        assert_eq!(allocator.free_blocks_of_size(64), 1);
+
+        allocator.dealloc(b, layout);
+        assert_eq!(allocator.free_blocks_of_size(64), 0);
+        assert_eq!(allocator.free_blocks_of_size(1024), 1, "Les compagnons auraient dû fusionner jusqu'au tas entier.");
+    }
+}
+
+#[test]
+fn test_paire_liberee_reconstitue_le_grand_bloc() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let allocator = BuddyAllocator::new();
+    let half = Layout::from_size_align(512, 8).unwrap();
+    let whole = Layout::from_size_align(1024, 8).unwrap();
+
+    unsafe {
+        allocator.init(start, size);
+
+        let a = allocator.alloc(half);
+        let b = allocator.alloc(half);
+        assert!(!a.is_null() && !b.is_null(), "L'allocation a échoué.");
+        assert!(allocator.alloc(whole).is_null(), "Le tas devrait être plein.");
+
+        allocator.dealloc(b, half);
+        allocator.dealloc(a, half);
+        assert_eq!(allocator.alloc(whole) as usize, start);
+    }
+}
@@ -0,0 +1,66 @@
+use allocateurharjit::{BumpAllocator, StaticHeap};
+use core::alloc::{GlobalAlloc, Layout};
+
+#[test]
+fn test_adresses_croissantes() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let allocator = BumpAllocator::new();
+
+    unsafe {
+        allocator.init(start, size);
+
+        // Chaque allocation suit la précédente, alignement compris.
+        let a = allocator.alloc(Layout::from_size_align(10, 1).unwrap());
+        let b = allocator.alloc(Layout::from_size_align(16, 8).unwrap());
+        let c = allocator.alloc(Layout::from_size_align(1, 1).unwrap());
+        assert_eq!(a as usize, start);
+        assert_eq!(b as usize, start + 16);
+        assert_eq!(c as usize, start + 32);
+        assert_eq!(allocator.remaining(), 1024 - 33);
+
+        // Une requête qui dépasse la région échoue sans rien consommer.
+        assert!(allocator.alloc(Layout::from_size_align(1024, 8).unwrap()).is_null());
+        assert_eq!(allocator.remaining(), 1024 - 33);
+    }
+}
+
+#[test]
+fn test_liberation_sans_effet() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let allocator = BumpAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(start, size);
+
+        let a = allocator.alloc(layout);
+        allocator.dealloc(a, layout);
+        assert_eq!(allocator.remaining(), 1024 - 64);
+
+        // La place libérée n'est pas réutilisée.
+        let b = allocator.alloc(layout);
+        assert_eq!(b as usize, a as usize + 64);
+    }
+}
+
+#[test]
+fn test_reinitialisation_rend_toute_la_region() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let allocator = BumpAllocator::new();
+    let layout = Layout::from_size_align(256, 8).unwrap();
+
+    unsafe {
+        allocator.init(start, size);
+        for _ in 0..4 {
+            assert!(!allocator.alloc(layout).is_null());
+        }
+        assert!(allocator.alloc(layout).is_null(), "La région devrait être épuisée.");
+
+        allocator.reset();
+        assert_eq!(allocator.remaining(), 1024);
+        assert_eq!(allocator.alloc(layout) as usize, start);
+    }
+}
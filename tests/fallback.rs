@@ -0,0 +1,33 @@
+use allocateurharjit::{Fallback, FreeListAllocator, Owns, StaticHeap};
+use core::alloc::{GlobalAlloc, Layout};
+
+#[test]
+fn test_debordement_vers_le_second_allocateur() {
+    let small_heap = StaticHeap::<256>::new();
+    let large_heap = StaticHeap::<1024>::new();
+    let chain = Fallback::new(FreeListAllocator::new(), FreeListAllocator::new());
+    let small = Layout::from_size_align(128, 8).unwrap();
+    let large = Layout::from_size_align(512, 8).unwrap();
+
+    unsafe {
+        let (start, size) = small_heap.as_region();
+        chain.primary().init(start, size);
+        let (start, size) = large_heap.as_region();
+        chain.secondary().init(start, size);
+
+        let a = chain.alloc(small);
+        assert!(chain.primary().owns(a), "La petite requête devrait tenir dans le premier tas.");
+
+        // Le premier tas est trop petit : la requête passe au second.
+        let b = chain.alloc(large);
+        assert!(!b.is_null(), "L'allocation de secours a échoué.");
+        assert!(chain.secondary().owns(b), "La grande requête aurait dû aboutir dans le second tas.");
+
+        // Chaque pointeur retourne à son propre allocateur.
+        chain.dealloc(b, large);
+        chain.dealloc(a, small);
+        assert_eq!(chain.primary().stats().allocated_bytes, 0);
+        assert_eq!(chain.secondary().stats().allocated_bytes, 0);
+        assert_eq!(chain.secondary().total_free(), 1024);
+    }
+}
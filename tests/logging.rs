@@ -0,0 +1,53 @@
+use allocateurharjit::{Event, FreeListAllocator, Logging, StaticHeap};
+use core::alloc::{GlobalAlloc, Layout};
+use std::sync::Mutex;
+
+// Événement reçu : (allocation ?, adresse, taille demandée)
+type Entry = (bool, usize, usize);
+
+// Événements reçus et leur nombre
+static EVENTS: Mutex<([Entry; 8], usize)> = Mutex::new(([(false, 0, 0); 8], 0));
+
+fn record(event: Event) {
+    let entry = match event {
+        Event::Alloc { layout, ptr } => (true, ptr as usize, layout.size()),
+        Event::Dealloc { layout, ptr } => (false, ptr as usize, layout.size()),
+    };
+
+    let mut events = EVENTS.lock().unwrap();
+    let (buffer, count) = &mut *events;
+    buffer[*count] = entry;
+    *count += 1;
+}
+
+#[test]
+fn test_journal_des_operations() {
+    let heap = StaticHeap::<1024>::new();
+    let allocator = Logging::new(FreeListAllocator::new(), record);
+    let small = Layout::from_size_align(32, 8).unwrap();
+    let large = Layout::from_size_align(2048, 8).unwrap();
+
+    unsafe {
+        let (start, size) = heap.as_region();
+        allocator.inner().init(start, size);
+
+        let a = allocator.alloc(small);
+        let b = allocator.alloc(small);
+        allocator.dealloc(a, small);
+        let failed = allocator.alloc(large);
+        allocator.dealloc(b, small);
+        assert!(failed.is_null());
+
+        // Chaque opération est signalée dans l'ordre, échec compris.
+        let (buffer, count) = *EVENTS.lock().unwrap();
+        let expected = [
+            (true, a as usize, 32),
+            (true, b as usize, 32),
+            (false, a as usize, 32),
+            (true, 0, 2048),
+            (false, b as usize, 32),
+        ];
+        assert_eq!(&buffer[..count], &expected);
+        assert_eq!(allocator.inner().total_free(), 1024);
+    }
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "std")]
+
+use allocateurharjit::{FreeListAllocator, Magazine, StaticHeap, MAGAZINE_SIZE};
+use core::alloc::{GlobalAlloc, Layout};
+
+#[test]
+fn test_va_et_vient_servi_par_le_magasin() {
+    static HEAP: StaticHeap<4096> = StaticHeap::new();
+    let allocator = Magazine::new(FreeListAllocator::new());
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        let (start, size) = HEAP.as_region();
+        allocator.inner().init(start, size);
+
+        let mut previous = None;
+        for i in 0..1000 {
+            let ptr = allocator.alloc(layout);
+            assert!(!ptr.is_null());
+            ptr.write_bytes(i as u8, layout.size());
+            assert!(previous.is_none_or(|previous| previous == ptr), "Le bloc libéré aurait dû être repris.");
+            previous = Some(ptr);
+            allocator.dealloc(ptr, layout);
+        }
+
+        // Seule la première allocation a atteint la liste centrale.
+        assert_eq!(allocator.central_operations(), 1);
+        assert_eq!(allocator.inner().stats().allocated_bytes, 64);
+
+        allocator.flush();
+        assert_eq!(allocator.inner().stats().allocated_bytes, 0);
+        assert_eq!(allocator.inner().count_free_blocks(), 1);
+    }
+}
+
+#[test]
+fn test_magasin_plein_vide_dans_la_liste_centrale() {
+    static HEAP: StaticHeap<4096> = StaticHeap::new();
+    let allocator = Magazine::new(FreeListAllocator::new());
+    let layout = Layout::from_size_align(32, 8).unwrap();
+
+    unsafe {
+        let (start, size) = HEAP.as_region();
+        allocator.inner().init(start, size);
+
+        let ptrs: Vec<_> = (0..MAGAZINE_SIZE + 1).map(|_| allocator.alloc(layout)).collect();
+        for &ptr in &ptrs {
+            allocator.dealloc(ptr, layout);
+        }
+
+        // Le dernier bloc a trouvé le magasin plein : les autres sont repartis au centre.
+        assert_eq!(allocator.central_operations(), 2 * MAGAZINE_SIZE + 1);
+        assert_eq!(allocator.inner().stats().allocated_bytes, 32);
+
+        // Un autre layout passe directement par la liste centrale.
+        let other = Layout::from_size_align(128, 8).unwrap();
+        allocator.dealloc(allocator.alloc(other), other);
+        assert_eq!(allocator.central_operations(), 2 * MAGAZINE_SIZE + 3);
+
+        allocator.flush();
+        assert_eq!(allocator.inner().stats().allocated_bytes, 0);
+    }
+}
+
+#[test]
+fn test_magasins_de_plusieurs_threads() {
+    static HEAP: StaticHeap<8192> = StaticHeap::new();
+    static ALLOCATOR: Magazine<FreeListAllocator> = Magazine::new(FreeListAllocator::new());
+
+    let (start, size) = HEAP.as_region();
+    unsafe { ALLOCATOR.inner().init(start, size) };
+
+    let threads: Vec<_> = (0..4)
+        .map(|t| {
+            std::thread::spawn(move || {
+                let layout = Layout::from_size_align(16 + 16 * t, 8).unwrap();
+                for i in 0..500 {
+                    unsafe {
+                        let ptr = ALLOCATOR.alloc(layout);
+                        assert!(!ptr.is_null());
+                        ptr.write_bytes(i as u8, layout.size());
+                        assert_eq!(*ptr.add(layout.size() - 1), i as u8);
+                        ALLOCATOR.dealloc(ptr, layout);
+                    }
+                }
+                ALLOCATOR.flush();
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    // Chaque thread n'a touché la liste centrale qu'à sa première allocation et à sa vidange.
+    assert_eq!(ALLOCATOR.central_operations(), 4 * 2);
+    assert_eq!(ALLOCATOR.inner().stats().allocated_bytes, 0);
+    assert_eq!(ALLOCATOR.inner().count_free_blocks(), 1);
+}
+
+#[test]
+fn test_fin_de_thread_vide_le_magasin() {
+    static HEAP: StaticHeap<4096> = StaticHeap::new();
+    static ALLOCATOR: Magazine<FreeListAllocator> = Magazine::new(FreeListAllocator::new());
+
+    let (start, size) = HEAP.as_region();
+    unsafe { ALLOCATOR.inner().init(start, size) };
+
+    // Le thread se termine sans `flush` : ses blocs gardés retournent tout de même au centre.
+    std::thread::spawn(|| unsafe {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let ptrs: Vec<_> = (0..4).map(|_| ALLOCATOR.alloc(layout)).collect();
+        for &ptr in &ptrs {
+            ALLOCATOR.dealloc(ptr, layout);
+        }
+        assert_eq!(ALLOCATOR.inner().stats().allocated_bytes, 4 * 64);
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(ALLOCATOR.inner().stats().allocated_bytes, 0);
+    assert_eq!(ALLOCATOR.inner().count_free_blocks(), 1);
+}
@@ -0,0 +1,82 @@
+use allocateurharjit::{FreeRange, OutOfLineAllocator, StaticHeap};
+use core::alloc::{GlobalAlloc, Layout};
+
+#[test]
+fn test_ecrasement_du_tas_sans_effet_sur_la_liste() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let mut metadata = [FreeRange::default(); 8];
+    let allocator = OutOfLineAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(start, size, metadata.as_mut_ptr(), metadata.len());
+
+        let a = allocator.alloc(layout);
+        let b = allocator.alloc(layout);
+        let c = allocator.alloc(layout);
+        allocator.dealloc(b, layout);
+        assert_eq!(allocator.free_range_count(), 2);
+
+        // On écrase tout le tas, zones libres comprises : aucune métadonnée n'y réside.
+        core::ptr::write_bytes(start as *mut u8, 0xFF, size);
+        assert_eq!(allocator.free_range_count(), 2);
+        assert_eq!(allocator.free_bytes(), 1024 - 128);
+        assert_eq!(metadata[..2], [FreeRange { start: start + 64, size: 64 }, FreeRange { start: start + 192, size: 832 }]);
+
+        // La place libérée est réattribuée, puis tout se refond en une seule plage.
+        assert_eq!(allocator.alloc(layout), b);
+        for ptr in [a, b, c] {
+            allocator.dealloc(ptr, layout);
+        }
+        assert_eq!(allocator.free_range_count(), 1);
+        assert_eq!(allocator.free_bytes(), 1024);
+    }
+}
+
+#[test]
+fn test_alignement_decoupe_la_plage() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let mut metadata = [FreeRange::default(); 8];
+    let allocator = OutOfLineAllocator::new();
+
+    unsafe {
+        allocator.init(start, size, metadata.as_mut_ptr(), metadata.len());
+
+        // L'écart laissé par l'alignement reste une plage libre à part.
+        let a = allocator.alloc(Layout::from_size_align(8, 1).unwrap());
+        let b = allocator.alloc(Layout::from_size_align(16, 16).unwrap());
+        assert_eq!(a as usize, start);
+        assert_eq!(b as usize, start + 16);
+        assert_eq!(allocator.free_range_count(), 2);
+        assert_eq!(allocator.free_bytes(), 1024 - 24);
+    }
+}
+
+#[test]
+fn test_descripteurs_epuises() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let mut metadata = [FreeRange::default(); 2];
+    let allocator = OutOfLineAllocator::new();
+    let layout = Layout::from_size_align(64, 8).unwrap();
+
+    unsafe {
+        allocator.init(start, size, metadata.as_mut_ptr(), metadata.len());
+
+        let ptrs: Vec<_> = (0..5).map(|_| allocator.alloc(layout)).collect();
+        allocator.dealloc(ptrs[1], layout);
+        assert_eq!(allocator.free_range_count(), 2);
+
+        // Plus aucun descripteur : la zone rendue ne peut pas être décrite et se perd.
+        allocator.dealloc(ptrs[3], layout);
+        assert_eq!(allocator.lost_bytes(), 64);
+        assert_eq!(allocator.free_bytes(), 1024 - 256);
+
+        // Une zone voisine d'une plage existante s'y fond sans nouveau descripteur.
+        allocator.dealloc(ptrs[0], layout);
+        assert_eq!(allocator.free_range_count(), 2);
+        assert_eq!(allocator.free_bytes(), 1024 - 192);
+    }
+}
@@ -0,0 +1,48 @@
+use allocateurharjit::{Slab, StaticHeap};
+
+#[test]
+fn test_remplissage_de_tous_les_emplacements() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let slab = Slab::<48, 16>::new();
+
+    unsafe {
+        slab.init(start, size);
+    }
+
+    // 21 emplacements de 48 octets tiennent après les 16 octets de la table d'occupation.
+    let capacity = slab.capacity();
+    assert_eq!(capacity, 21);
+
+    let mut previous = 0;
+    for _ in 0..capacity {
+        let slot = slab.alloc_slot().expect("Un emplacement devrait être libre.").as_ptr() as usize;
+        assert_eq!(slot % 16, 0, "L'emplacement n'est pas aligné.");
+        assert!(slot + 48 <= start + size, "L'emplacement déborde de la région.");
+        assert!(slot > previous, "Deux emplacements se recouvrent.");
+        previous = slot;
+    }
+
+    assert_eq!(slab.free_slots(), 0);
+    assert!(slab.alloc_slot().is_none(), "Le slab devrait être plein.");
+}
+
+#[test]
+fn test_reutilisation_d_un_emplacement_libere() {
+    let heap = StaticHeap::<1024>::new();
+    let (start, size) = heap.as_region();
+    let slab = Slab::<32, 8>::new();
+
+    unsafe {
+        slab.init(start, size);
+
+        let slots: Vec<_> = (0..slab.capacity()).map(|_| slab.alloc_slot().unwrap()).collect();
+        let middle = slots[slots.len() / 2];
+
+        // L'emplacement libéré au milieu est le seul disponible, il est donc réattribué.
+        slab.free_slot(middle.as_ptr());
+        assert_eq!(slab.free_slots(), 1);
+        assert_eq!(slab.alloc_slot(), Some(middle));
+        assert!(slab.alloc_slot().is_none());
+    }
+}